@@ -1,21 +1,25 @@
 use core::iter;
+use core::mem::MaybeUninit;
 
-use crate::utils::{checked, min_and_1, saturating};
+use crate::alternating_all::Next;
+use crate::utils::{checked, fill, fill_uninit, min_and_1, saturating, Observer, Rotation};
 #[allow(unused_imports)]
 use crate::AlternatingExt;
+use crate::{AlternatingAll, AlternatingMany, AlternatingNoRemainder, Mode, Side};
 
 /// Struct for alternating between the items of two iterators.
 ///
 /// This struct is created by the [`AlternatingExt::alternate_with`] method, see its documentation for more.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Alternating<I, J>
+pub struct Alternating<I, J, F = fn(Side)>
 where
     I: Iterator,
     J: Iterator<Item = I::Item>,
 {
-    i: I,
-    j: J,
-    i_next: bool,
+    pub(crate) i: I,
+    pub(crate) j: J,
+    pub(crate) i_next: bool,
+    pub(crate) observer: Observer<F>,
 }
 
 impl<I, J> Alternating<I, J>
@@ -31,23 +35,124 @@ where
             i: i.into_iter(),
             j: j.into_iter(),
             i_next: true,
+            observer: Observer::new(),
         }
     }
 }
 
-impl<I, J> Iterator for Alternating<I, J>
+impl<I, J, F> Alternating<I, J, F>
 where
     I: Iterator,
     J: Iterator<Item = I::Item>,
+{
+    /// Number of items returned from the left iterator so far.
+    pub fn yielded_left(&self) -> usize {
+        self.observer.left
+    }
+
+    /// Number of items returned from the right iterator so far.
+    pub fn yielded_right(&self) -> usize {
+        self.observer.right
+    }
+
+    /// The first iterator that returned `None`, if any.
+    ///
+    /// Since alternation continues after exhaustion, the other iterator may also be exhausted by now.
+    pub fn exhausted(&self) -> Option<Side> {
+        self.observer.exhausted
+    }
+
+    /// Call `hook` the first time one of the iterators returns `None`.
+    ///
+    /// The hook is never called if an iterator was already found to be exhausted.
+    pub fn on_exhausted<G>(self, hook: G) -> Alternating<I, J, G>
+    where
+        G: FnMut(Side),
+    {
+        Alternating {
+            i: self.i,
+            j: self.j,
+            i_next: self.i_next,
+            observer: self.observer.with_hook(hook),
+        }
+    }
+
+    /// Continue as an [`AlternatingAll`], draining the other iterator once one is exhausted.
+    ///
+    /// The next item comes from the same iterator it would have come from without the conversion.
+    pub fn into_all(self) -> AlternatingAll<I, J, F> {
+        AlternatingAll {
+            i: self.i,
+            j: self.j,
+            next: if self.i_next { Next::I } else { Next::J },
+            observer: self.observer,
+        }
+    }
+
+    /// Continue as an [`AlternatingNoRemainder`], stopping once an iterator is exhausted.
+    ///
+    /// The next item comes from the same iterator it would have come from without the conversion.
+    pub fn into_no_remainder(self) -> AlternatingNoRemainder<I, J, F> {
+        AlternatingNoRemainder {
+            i: self.i,
+            j: self.j,
+            last_i: !self.i_next,
+            observer: self.observer,
+        }
+    }
+
+    /// Add `other` to the rotation, continuing as an [`AlternatingMany`] in which it takes its turn
+    /// after the right iterator, so that all three get the same share of turns.
+    ///
+    /// See [`AlternatingAll::alternate_also`] for more.
+    /// Exhausted iterators keep returning `None` on their turn.
+    pub fn alternate_also<K>(self, other: K) -> AlternatingMany<(I, J, K::IntoIter)>
+    where
+        K: IntoIterator<Item = I::Item>,
+    {
+        let next = if self.i_next { Side::Left } else { Side::Right };
+        let rotation = Rotation::from_two_way(Mode::Gapped, next, None);
+        AlternatingMany::from_parts((self.i, self.j, other.into_iter()), rotation)
+    }
+}
+
+impl<I, J, F> Alternating<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
+{
+    /// Write the next items into `buf`, returning how many were written.
+    ///
+    /// Writing stops when `buf` is full or at the first `None`,
+    /// and the iterator can be resumed afterwards, for instance to continue past the `None`.
+    pub fn alternate_into(&mut self, buf: &mut [I::Item]) -> usize {
+        fill(self, buf)
+    }
+
+    /// Write the next items into `buf`, returning how many were written,
+    /// like [`alternate_into`](Alternating::alternate_into).
+    ///
+    /// Exactly the first that many elements of `buf` are initialized by this call.
+    pub fn alternate_into_uninit(&mut self, buf: &mut [MaybeUninit<I::Item>]) -> usize {
+        fill_uninit(self, buf)
+    }
+}
+
+impl<I, J, F> Iterator for Alternating<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
 {
     type Item = I::Item;
     fn next(&mut self) -> Option<Self::Item> {
         if self.i_next {
             self.i_next = false;
-            self.i.next()
+            self.observer.record(Side::Left, self.i.next())
         } else {
             self.i_next = true;
-            self.j.next()
+            self.observer.record(Side::Right, self.j.next())
         }
     }
 
@@ -75,6 +180,50 @@ where
     }
 }
 
+impl<I, J, F> Alternating<iter::Peekable<I>, iter::Peekable<J>, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
+{
+    /// The iterator the next item comes from, or `None` if [`next`](Iterator::next) would return `None`.
+    ///
+    /// Requires both iterators to be [`Peekable`](iter::Peekable).
+    pub fn peek_side(&mut self) -> Option<Side> {
+        if self.i_next {
+            self.i.peek().map(|_| Side::Left)
+        } else {
+            self.j.peek().map(|_| Side::Right)
+        }
+    }
+
+    /// Peek at the next item of the left iterator, regardless of whose turn it is.
+    pub fn peek_left(&mut self) -> Option<&I::Item> {
+        self.i.peek()
+    }
+
+    /// Peek at the next item of the right iterator, regardless of whose turn it is.
+    pub fn peek_right(&mut self) -> Option<&I::Item> {
+        self.j.peek()
+    }
+
+    /// Peek at the next item along with the iterator it comes from.
+    pub fn peek(&mut self) -> Option<(Side, &I::Item)> {
+        match self.peek_side()? {
+            Side::Left => self.i.peek().map(|item| (Side::Left, item)),
+            Side::Right => self.j.peek().map(|item| (Side::Right, item)),
+        }
+    }
+
+    /// Consume and return the next item if `func` returns `true` for it and its side.
+    pub fn next_if(&mut self, func: impl FnOnce(Side, &I::Item) -> bool) -> Option<I::Item> {
+        match self.peek() {
+            Some((side, item)) if func(side, item) => self.next(),
+            _ => None,
+        }
+    }
+}
+
 // Deprecated: According to the documentation for ExactSizeIterator,
 // "If an adapter makes an iterator longer, then it’s usually incorrect for
 // that adapter to implement ExactSizeIterator."
@@ -331,4 +480,101 @@ mod tests {
 
         assert_eq!(iter.size_hint(), (usize::MAX, None));
     }
+
+    #[test]
+    fn observe_exhaustion() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut hooked = Vec::new();
+        let mut iter = a
+            .iter()
+            .alternate_with(b.iter())
+            .on_exhausted(|side| hooked.push(side));
+
+        assert_eq!(iter.by_ref().take(4).count(), 4);
+        assert_eq!(iter.exhausted(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.exhausted(), Some(Side::Left));
+        assert_eq!(iter.next(), Some(&5));
+        no_more(&mut iter, DEFAULT_ATTEMPT);
+        assert_eq!(iter.exhausted(), Some(Side::Left));
+        assert_eq!((iter.yielded_left(), iter.yielded_right()), (2, 3));
+        assert_eq!(hooked, [Side::Left]);
+    }
+
+    #[test]
+    fn peek() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = a.iter().peekable().alternate_with(b.iter().peekable());
+
+        assert_eq!(iter.peek(), Some((Side::Left, &&1)));
+        assert_eq!(iter.peek_right(), Some(&&3));
+        assert_eq!(iter.next_if(|side, _| side == Side::Right), None);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_if(|_, &&item| item == 3), Some(&3));
+        assert_eq!(iter.by_ref().take(2).count(), 2);
+        assert_eq!(iter.peek_side(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.peek(), Some((Side::Right, &&5)));
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.peek_left(), None);
+    }
+
+    #[test]
+    fn into_all() {
+        let a = [1, 2];
+        let b = [3, 4, 5, 6];
+
+        let mut iter = a.iter().alternate_with(b.iter());
+
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+
+        let mut iter = iter.into_all();
+
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.next(), Some(&6));
+        assert_eq!(iter.next(), None);
+        assert_eq!((iter.yielded_left(), iter.yielded_right()), (2, 4));
+    }
+
+    #[test]
+    fn into_no_remainder() {
+        let a = [1, 2, 3];
+        let b = [4];
+
+        let mut iter = a.iter().alternate_with(b.iter());
+
+        assert_eq!(iter.next(), Some(&1));
+
+        let mut iter = iter.into_no_remainder();
+
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.exhausted(), Some(Side::Right));
+    }
+
+    #[test]
+    fn alternate_into() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = a.iter().alternate_with(b.iter());
+        let mut buf = [&0; 3];
+
+        assert_eq!(iter.alternate_into(&mut buf), 3);
+        assert_eq!(buf, [&1, &3, &2]);
+        assert_eq!(iter.alternate_into(&mut buf), 1);
+        assert_eq!(buf[..1], [&4]);
+        // Resume past the gap left by `a`.
+        assert_eq!(iter.alternate_into(&mut buf), 1);
+        assert_eq!(buf[..1], [&5]);
+        assert_eq!(iter.alternate_into(&mut buf), 0);
+    }
 }
@@ -1,21 +1,25 @@
 use core::iter;
+use core::mem::MaybeUninit;
 
+use crate::utils::{fill, fill_uninit, Observer, Rotation};
 #[allow(unused_imports)]
 use crate::AlternatingExt;
+use crate::{Alternating, AlternatingMany, AlternatingNoRemainder, Mode, Side};
 
 /// Struct for alternating between the items of two iterators while handling size difference intuitively.
 ///
 /// This struct is created by the [`AlternatingExt::alternate_with_all`] method, see its documentation for more.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct AlternatingAll<I, J> {
-    i: I,
-    j: J,
-    next: Next,
+pub struct AlternatingAll<I, J, F = fn(Side)> {
+    pub(crate) i: I,
+    pub(crate) j: J,
+    pub(crate) next: Next,
+    pub(crate) observer: Observer<F>,
 }
 
 /// Represent the next iterator to be used.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Next {
+pub(crate) enum Next {
     I,
     J,
     /// Marks that iterator `j` has been exhausted
@@ -37,39 +41,181 @@ where
             i: i.into_iter(),
             j: j.into_iter(),
             next: Next::I,
+            observer: Observer::new(),
         }
     }
 }
 
-impl<I, J> Iterator for AlternatingAll<I, J>
+impl<I, J, F> AlternatingAll<I, J, F>
 where
     I: Iterator,
     J: Iterator<Item = I::Item>,
+{
+    /// Number of items returned from the left iterator so far.
+    pub fn yielded_left(&self) -> usize {
+        self.observer.left
+    }
+
+    /// Number of items returned from the right iterator so far.
+    pub fn yielded_right(&self) -> usize {
+        self.observer.right
+    }
+
+    /// The iterator that was exhausted first, after which only the other one is used.
+    pub fn exhausted(&self) -> Option<Side> {
+        self.observer.exhausted
+    }
+
+    /// Call `hook` when one of the iterators is exhausted and only the other one remains.
+    ///
+    /// The hook is never called if an iterator was already exhausted.
+    pub fn on_exhausted<G>(self, hook: G) -> AlternatingAll<I, J, G>
+    where
+        G: FnMut(Side),
+    {
+        AlternatingAll {
+            i: self.i,
+            j: self.j,
+            next: self.next,
+            observer: self.observer.with_hook(hook),
+        }
+    }
+
+    /// Continue as an [`Alternating`], alternating even after an iterator is exhausted.
+    ///
+    /// The next item comes from the same iterator it would have come from without the conversion.
+    pub fn into_gapped(self) -> Alternating<I, J, F> {
+        Alternating {
+            i: self.i,
+            j: self.j,
+            i_next: matches!(self.next, Next::I | Next::IAlways),
+            observer: self.observer,
+        }
+    }
+
+    /// Continue as an [`AlternatingNoRemainder`], stopping once an iterator is exhausted.
+    ///
+    /// If an iterator is already exhausted, the iteration stops immediately.
+    pub fn into_no_remainder(self) -> AlternatingNoRemainder<I, J, F> {
+        AlternatingNoRemainder {
+            i: self.i,
+            j: self.j,
+            // When one iterator is exhausted, its turn is next so that the iteration stops.
+            last_i: matches!(self.next, Next::J | Next::IAlways),
+            observer: self.observer,
+        }
+    }
+
+    /// Add `other` to the rotation, continuing as an [`AlternatingMany`] in which it takes its turn
+    /// after the right iterator, so that all three get the same share of turns.
+    ///
+    /// Alternating with `other` instead would give it every other item, half of all turns.
+    /// The next item comes from the same iterator it would have come from without the conversion,
+    /// and exhaustion is still handled in the same way, but the counts and the hook are not carried over.
+    /// More iterators can be added with [`AlternatingMany::alternate_also`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let nested = (1..3).alternate_with_all(10..12).alternate_with_all(20..22);
+    /// assert_eq!(nested.collect::<Vec<_>>(), [1, 20, 10, 21, 2, 11]);
+    ///
+    /// let fair = (1..3).alternate_with_all(10..12).alternate_also(20..22);
+    /// assert_eq!(fair.collect::<Vec<_>>(), [1, 10, 20, 2, 11, 21]);
+    ///
+    /// let four = (1..3).alternate_with_all(10..12).alternate_also(20..21).alternate_also(30..33);
+    /// assert_eq!(four.collect::<Vec<_>>(), [1, 10, 20, 30, 2, 11, 31, 32]);
+    /// ```
+    pub fn alternate_also<K>(self, other: K) -> AlternatingMany<(I, J, K::IntoIter)>
+    where
+        K: IntoIterator<Item = I::Item>,
+    {
+        let (next, exhausted) = match self.next {
+            Next::I => (Side::Left, None),
+            Next::J => (Side::Right, None),
+            Next::IAlways => (Side::Left, Some(Side::Right)),
+            Next::JAlways => (Side::Right, Some(Side::Left)),
+        };
+        let rotation = Rotation::from_two_way(Mode::All, next, exhausted);
+        AlternatingMany::from_parts((self.i, self.j, other.into_iter()), rotation)
+    }
+}
+
+impl<I, J, F> AlternatingAll<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
+{
+    /// Write the next items into `buf`, returning how many were written.
+    ///
+    /// Writing stops when `buf` is full or when both iterators are exhausted,
+    /// and the iterator can be resumed afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let a = [1, 2];
+    /// let b = [3, 4, 5];
+    ///
+    /// let mut iter = a.iter().copied().alternate_with_all(b.iter().copied());
+    /// let mut frame = [0; 2];
+    ///
+    /// assert_eq!(iter.alternate_into(&mut frame), 2);
+    /// assert_eq!(frame, [1, 3]);
+    /// assert_eq!(iter.alternate_into(&mut frame), 2);
+    /// assert_eq!(frame, [2, 4]);
+    /// assert_eq!(iter.alternate_into(&mut frame), 1);
+    /// assert_eq!(frame[..1], [5]);
+    /// assert_eq!(iter.alternate_into(&mut frame), 0);
+    /// ```
+    pub fn alternate_into(&mut self, buf: &mut [I::Item]) -> usize {
+        fill(self, buf)
+    }
+
+    /// Write the next items into `buf`, returning how many were written,
+    /// like [`alternate_into`](AlternatingAll::alternate_into).
+    ///
+    /// Exactly the first that many elements of `buf` are initialized by this call.
+    pub fn alternate_into_uninit(&mut self, buf: &mut [MaybeUninit<I::Item>]) -> usize {
+        fill_uninit(self, buf)
+    }
+}
+
+impl<I, J, F> Iterator for AlternatingAll<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
 {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.next {
             Next::I => {
-                if let Some(item) = self.i.next() {
+                if let Some(item) = self.observer.record(Side::Left, self.i.next()) {
                     self.next = Next::J;
                     Some(item)
                 } else {
                     self.next = Next::JAlways;
-                    self.j.next()
+                    self.observer.record(Side::Right, self.j.next())
                 }
             }
             Next::J => {
-                if let Some(item) = self.j.next() {
+                if let Some(item) = self.observer.record(Side::Right, self.j.next()) {
                     self.next = Next::I;
                     Some(item)
                 } else {
                     self.next = Next::IAlways;
-                    self.i.next()
+                    self.observer.record(Side::Left, self.i.next())
                 }
             }
-            Next::IAlways => self.i.next(),
-            Next::JAlways => self.j.next(),
+            Next::IAlways => self.observer.record(Side::Left, self.i.next()),
+            Next::JAlways => self.observer.record(Side::Right, self.j.next()),
         }
     }
 
@@ -83,6 +229,72 @@ where
     }
 }
 
+impl<I, J, F> AlternatingAll<iter::Peekable<I>, iter::Peekable<J>, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
+{
+    /// The iterator the next item comes from, or `None` if both are exhausted.
+    ///
+    /// Requires both iterators to be [`Peekable`](iter::Peekable).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::{AlternatingExt, Side};
+    ///
+    /// let a = [1, 2];
+    /// let b = [3, 4, 5];
+    ///
+    /// let mut iter = a.iter().peekable().alternate_with_all(b.iter().peekable());
+    ///
+    /// assert_eq!(iter.peek_side(), Some(Side::Left));
+    /// assert_eq!(iter.peek_right(), Some(&&3)); // look ahead at `b`
+    /// assert_eq!(iter.next_if(|side, _| side == Side::Right), None);
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.peek(), Some((Side::Right, &&3)));
+    /// ```
+    pub fn peek_side(&mut self) -> Option<Side> {
+        let (first, second) = match self.next {
+            Next::I => (Side::Left, Side::Right),
+            Next::J => (Side::Right, Side::Left),
+            Next::IAlways => return self.i.peek().map(|_| Side::Left),
+            Next::JAlways => return self.j.peek().map(|_| Side::Right),
+        };
+        [first, second].into_iter().find(|side| match side {
+            Side::Left => self.i.peek().is_some(),
+            Side::Right => self.j.peek().is_some(),
+        })
+    }
+
+    /// Peek at the next item of the left iterator, regardless of whose turn it is.
+    pub fn peek_left(&mut self) -> Option<&I::Item> {
+        self.i.peek()
+    }
+
+    /// Peek at the next item of the right iterator, regardless of whose turn it is.
+    pub fn peek_right(&mut self) -> Option<&I::Item> {
+        self.j.peek()
+    }
+
+    /// Peek at the next item along with the iterator it comes from.
+    pub fn peek(&mut self) -> Option<(Side, &I::Item)> {
+        match self.peek_side()? {
+            Side::Left => self.i.peek().map(|item| (Side::Left, item)),
+            Side::Right => self.j.peek().map(|item| (Side::Right, item)),
+        }
+    }
+
+    /// Consume and return the next item if `func` returns `true` for it and its side.
+    pub fn next_if(&mut self, func: impl FnOnce(Side, &I::Item) -> bool) -> Option<I::Item> {
+        match self.peek() {
+            Some((side, item)) if func(side, item) => self.next(),
+            _ => None,
+        }
+    }
+}
+
 // Deprecated: According to the documentation for ExactSizeIterator,
 // "If an adapter makes an iterator longer, then it’s usually incorrect for
 // that adapter to implement ExactSizeIterator."
@@ -96,10 +308,11 @@ where
 //         self.i.len() + self.j.len()
 //     }
 // }
-impl<I, J> iter::FusedIterator for AlternatingAll<I, J>
+impl<I, J, F> iter::FusedIterator for AlternatingAll<I, J, F>
 where
     I: iter::FusedIterator,
     J: iter::FusedIterator<Item = I::Item>,
+    F: FnMut(Side),
 {
 }
 
@@ -265,4 +478,113 @@ mod tests {
 
         assert_eq!(iter.size_hint(), (usize::MAX, Some(usize::MAX)));
     }
+
+    #[test]
+    fn observe_exhaustion() {
+        let a = [1, 2, 3];
+        let b = [4, 5];
+
+        let mut hooked = Vec::new();
+        let mut iter = a
+            .iter()
+            .alternate_with_all(b.iter())
+            .on_exhausted(|side| hooked.push(side));
+
+        assert_eq!(iter.by_ref().take(5).count(), 5);
+        assert_eq!(iter.exhausted(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.exhausted(), Some(Side::Right));
+        assert_eq!((iter.yielded_left(), iter.yielded_right()), (3, 2));
+        assert_eq!(hooked, [Side::Right]);
+    }
+
+    #[test]
+    fn peek() {
+        let a = [1, 2, 3];
+        let b = [4];
+
+        let mut iter = a.iter().peekable().alternate_with_all(b.iter().peekable());
+
+        assert_eq!(iter.peek(), Some((Side::Left, &&1)));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.peek_side(), Some(Side::Right));
+        assert_eq!(iter.next_if(|_, &&item| item == 4), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        // `b` is exhausted, so the turn passes back to `a`.
+        assert_eq!(iter.peek(), Some((Side::Left, &&3)));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.peek_side(), None);
+        assert_eq!(iter.next_if(|_, _| true), None);
+    }
+
+    #[test]
+    fn into_gapped() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = a.iter().alternate_with_all(b.iter());
+
+        assert_eq!(iter.next(), Some(&1));
+
+        let mut iter = iter.into_gapped();
+
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), Some(&5));
+    }
+
+    #[test]
+    fn into_no_remainder() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = a.iter().alternate_with_all(b.iter());
+
+        assert_eq!(iter.next(), Some(&1));
+
+        let mut iter = iter.into_no_remainder();
+
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn into_no_remainder_after_exhaustion() {
+        let a = [1, 2, 3];
+        let b = [4];
+
+        let mut iter = a.iter().alternate_with_all(b.iter());
+
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+
+        let mut iter = iter.into_no_remainder();
+
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn alternate_into_uninit() {
+        use core::mem::MaybeUninit;
+
+        let a = [1, 2, 3];
+        let b = [4];
+
+        let mut iter = a.iter().alternate_with_all(b.iter());
+        let mut buf = [MaybeUninit::uninit(); 3];
+
+        assert_eq!(iter.alternate_into_uninit(&mut buf), 3);
+        // SAFETY: all 3 were written.
+        assert_eq!(buf.map(|item| unsafe { item.assume_init() }), [&1, &4, &2]);
+        assert_eq!(iter.alternate_into_uninit(&mut buf), 1);
+        // SAFETY: the first one was written.
+        assert_eq!(unsafe { buf[0].assume_init() }, &3);
+        assert_eq!(iter.alternate_into_uninit(&mut buf), 0);
+    }
 }
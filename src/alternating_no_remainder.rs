@@ -1,17 +1,21 @@
 use core::iter;
+use core::mem::MaybeUninit;
 
-use crate::utils::{checked, min_and_1, saturating};
+use crate::alternating_all::Next;
+use crate::utils::{checked, fill, fill_uninit, min_and_1, saturating, Observer, Rotation};
 #[allow(unused_imports)]
 use crate::AlternatingExt;
+use crate::{Alternating, AlternatingAll, AlternatingMany, Mode, Side};
 
 /// Struct for alternating between the items of two iterators until one is exhausted.
 ///
 /// This struct is created by the [`AlternatingExt::alternate_with_no_remainder`] method, see its documentation for more.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct AlternatingNoRemainder<I, J> {
-    i: I,
-    j: J,
-    last_i: bool,
+pub struct AlternatingNoRemainder<I, J, F = fn(Side)> {
+    pub(crate) i: I,
+    pub(crate) j: J,
+    pub(crate) last_i: bool,
+    pub(crate) observer: Observer<F>,
 }
 
 impl<I, J> AlternatingNoRemainder<I, J>
@@ -27,31 +31,124 @@ where
             i: i.into_iter(),
             j: j.into_iter(),
             last_i: false,
+            observer: Observer::new(),
         }
     }
 }
 
-impl<I, J> Iterator for AlternatingNoRemainder<I, J>
+impl<I, J, F> AlternatingNoRemainder<I, J, F>
 where
     I: Iterator,
     J: Iterator<Item = I::Item>,
+{
+    /// Number of items returned from the left iterator so far.
+    pub fn yielded_left(&self) -> usize {
+        self.observer.left
+    }
+
+    /// Number of items returned from the right iterator so far.
+    pub fn yielded_right(&self) -> usize {
+        self.observer.right
+    }
+
+    /// The iterator whose exhaustion stopped the iteration, if it has stopped.
+    pub fn exhausted(&self) -> Option<Side> {
+        self.observer.exhausted
+    }
+
+    /// Call `hook` when the iteration stops because one of the iterators returned `None`.
+    ///
+    /// The hook is never called if the iteration had already stopped.
+    pub fn on_exhausted<G>(self, hook: G) -> AlternatingNoRemainder<I, J, G>
+    where
+        G: FnMut(Side),
+    {
+        AlternatingNoRemainder {
+            i: self.i,
+            j: self.j,
+            last_i: self.last_i,
+            observer: self.observer.with_hook(hook),
+        }
+    }
+
+    /// Continue as an [`Alternating`], alternating even after an iterator is exhausted.
+    ///
+    /// The next item comes from the same iterator it would have come from without the conversion.
+    pub fn into_gapped(self) -> Alternating<I, J, F> {
+        Alternating {
+            i: self.i,
+            j: self.j,
+            i_next: !self.last_i,
+            observer: self.observer,
+        }
+    }
+
+    /// Continue as an [`AlternatingAll`], draining the other iterator once one is exhausted.
+    ///
+    /// The next item comes from the same iterator it would have come from without the conversion.
+    pub fn into_all(self) -> AlternatingAll<I, J, F> {
+        AlternatingAll {
+            i: self.i,
+            j: self.j,
+            next: if self.last_i { Next::J } else { Next::I },
+            observer: self.observer,
+        }
+    }
+
+    /// Add `other` to the rotation, continuing as an [`AlternatingMany`] in which it takes its turn
+    /// after the right iterator, so that all three get the same share of turns.
+    ///
+    /// See [`AlternatingAll::alternate_also`] for more.
+    /// The iteration still stops at the first exhausted iterator, or stays stopped if it already has.
+    pub fn alternate_also<K>(self, other: K) -> AlternatingMany<(I, J, K::IntoIter)>
+    where
+        K: IntoIterator<Item = I::Item>,
+    {
+        let next = if self.last_i { Side::Right } else { Side::Left };
+        let rotation = Rotation::from_two_way(Mode::NoRemainder, next, self.observer.exhausted);
+        AlternatingMany::from_parts((self.i, self.j, other.into_iter()), rotation)
+    }
+}
+
+impl<I, J, F> AlternatingNoRemainder<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
+{
+    /// Write the next items into `buf`, returning how many were written.
+    ///
+    /// Writing stops when `buf` is full or when the iteration stops,
+    /// and the iterator can be resumed afterwards.
+    pub fn alternate_into(&mut self, buf: &mut [I::Item]) -> usize {
+        fill(self, buf)
+    }
+
+    /// Write the next items into `buf`, returning how many were written,
+    /// like [`alternate_into`](AlternatingNoRemainder::alternate_into).
+    ///
+    /// Exactly the first that many elements of `buf` are initialized by this call.
+    pub fn alternate_into_uninit(&mut self, buf: &mut [MaybeUninit<I::Item>]) -> usize {
+        fill_uninit(self, buf)
+    }
+}
+
+impl<I, J, F> Iterator for AlternatingNoRemainder<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
 {
     type Item = I::Item;
     fn next(&mut self) -> Option<Self::Item> {
         if self.last_i {
-            if let Some(item) = self.j.next() {
-                self.last_i = false;
-                Some(item)
-            } else {
-                None
-            }
+            let item = self.observer.record(Side::Right, self.j.next())?;
+            self.last_i = false;
+            Some(item)
         } else {
-            if let Some(item) = self.i.next() {
-                self.last_i = true;
-                Some(item)
-            } else {
-                None
-            }
+            let item = self.observer.record(Side::Left, self.i.next())?;
+            self.last_i = true;
+            Some(item)
         }
     }
 
@@ -78,6 +175,50 @@ where
     }
 }
 
+impl<I, J, F> AlternatingNoRemainder<iter::Peekable<I>, iter::Peekable<J>, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(Side),
+{
+    /// The iterator the next item comes from, or `None` if the iteration has stopped.
+    ///
+    /// Requires both iterators to be [`Peekable`](iter::Peekable).
+    pub fn peek_side(&mut self) -> Option<Side> {
+        if self.last_i {
+            self.j.peek().map(|_| Side::Right)
+        } else {
+            self.i.peek().map(|_| Side::Left)
+        }
+    }
+
+    /// Peek at the next item of the left iterator, regardless of whose turn it is.
+    pub fn peek_left(&mut self) -> Option<&I::Item> {
+        self.i.peek()
+    }
+
+    /// Peek at the next item of the right iterator, regardless of whose turn it is.
+    pub fn peek_right(&mut self) -> Option<&I::Item> {
+        self.j.peek()
+    }
+
+    /// Peek at the next item along with the iterator it comes from.
+    pub fn peek(&mut self) -> Option<(Side, &I::Item)> {
+        match self.peek_side()? {
+            Side::Left => self.i.peek().map(|item| (Side::Left, item)),
+            Side::Right => self.j.peek().map(|item| (Side::Right, item)),
+        }
+    }
+
+    /// Consume and return the next item if `func` returns `true` for it and its side.
+    pub fn next_if(&mut self, func: impl FnOnce(Side, &I::Item) -> bool) -> Option<I::Item> {
+        match self.peek() {
+            Some((side, item)) if func(side, item) => self.next(),
+            _ => None,
+        }
+    }
+}
+
 // Deprecated: According to the documentation for ExactSizeIterator,
 // "If an adapter makes an iterator longer, then it’s usually incorrect for
 // that adapter to implement ExactSizeIterator."
@@ -91,10 +232,11 @@ where
 //         saturating(min_and_1(self.i.len(), self.j.len(), self.last_i))
 //     }
 // }
-impl<I, J> iter::FusedIterator for AlternatingNoRemainder<I, J>
+impl<I, J, F> iter::FusedIterator for AlternatingNoRemainder<I, J, F>
 where
     I: iter::FusedIterator,
     J: iter::FusedIterator<Item = I::Item>,
+    F: FnMut(Side),
 {
 }
 
@@ -327,4 +469,98 @@ mod tests {
 
         assert_eq!(iter.size_hint(), (usize::MAX, None));
     }
+
+    #[test]
+    fn observe_exhaustion() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut hooked = Vec::new();
+        let mut iter = b
+            .iter()
+            .alternate_with_no_remainder(a.iter())
+            .on_exhausted(|side| hooked.push(side));
+
+        assert_eq!(iter.by_ref().take(4).count(), 4);
+        assert_eq!(iter.exhausted(), None);
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.exhausted(), Some(Side::Right));
+        assert_eq!((iter.yielded_left(), iter.yielded_right()), (3, 2));
+        assert_eq!(hooked, [Side::Right]);
+    }
+
+    #[test]
+    fn peek() {
+        let a = [1, 2];
+        let b = [3];
+
+        let mut iter = a
+            .iter()
+            .peekable()
+            .alternate_with_no_remainder(b.iter().peekable());
+
+        assert_eq!(iter.peek(), Some((Side::Left, &&1)));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_if(|side, _| side == Side::Left), None);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.peek_side(), Some(Side::Left));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.peek_side(), None);
+        assert_eq!(iter.peek_left(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn into_gapped() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = a.iter().alternate_with_no_remainder(b.iter());
+
+        assert_eq!(iter.next(), Some(&1));
+
+        let mut iter = iter.into_gapped();
+
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), Some(&5));
+    }
+
+    #[test]
+    fn into_all() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = a.iter().alternate_with_no_remainder(b.iter());
+
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&3));
+
+        let mut iter = iter.into_all();
+
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn alternate_into() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = a.iter().alternate_with_no_remainder(b.iter());
+        let mut buf = [&0; 3];
+
+        assert_eq!(iter.alternate_into(&mut buf), 3);
+        assert_eq!(buf, [&1, &3, &2]);
+        assert_eq!(iter.alternate_into(&mut buf), 1);
+        assert_eq!(buf[..1], [&4]);
+        assert_eq!(iter.alternate_into(&mut buf), 0);
+        assert_eq!(iter.alternate_into(&mut []), 0);
+    }
 }
@@ -0,0 +1,281 @@
+use core::iter;
+
+#[allow(unused_imports)]
+use crate::AlternatingExt;
+use crate::Side;
+
+/// Struct for alternating between the items of two iterators in a random but reproducible order.
+///
+/// Once one of the iterators is exhausted, the remaining items from the other iterator
+/// are returned without interruption, like [`AlternatingAll`](crate::AlternatingAll).
+///
+/// This struct is created by the [`AlternatingExt::alternate_random`] and
+/// [`AlternatingExt::alternate_riffle`] methods, see their documentation for more.
+#[derive(Debug, Clone)]
+pub struct AlternatingRandom<I, J> {
+    i: I,
+    j: J,
+    rng: SplitMix64,
+    pick: Pick,
+    /// Marks that the other iterator has been exhausted
+    only: Option<Side>,
+}
+
+/// How the side of the next item is picked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pick {
+    /// Left with a fixed probability.
+    Probability(f64),
+    /// Left with a probability proportional to the remaining length of the left iterator.
+    Remaining,
+}
+
+impl<I, J> AlternatingRandom<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    /// Create a new `AlternatingRandom` iterator from two other iterables,
+    /// picking each side with equal probability.
+    ///
+    /// Alternative to [`AlternatingExt::alternate_random`]. There is no difference.
+    pub fn new(
+        i: impl IntoIterator<IntoIter = I>,
+        j: impl IntoIterator<IntoIter = J>,
+        seed: u64,
+    ) -> Self {
+        Self {
+            i: i.into_iter(),
+            j: j.into_iter(),
+            rng: SplitMix64(seed),
+            pick: Pick::Probability(0.5),
+            only: None,
+        }
+    }
+
+    /// Set the probability of picking the left iterator for each item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not within `0.0..=1.0`.
+    pub fn left_probability(mut self, p: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "probability must be within 0.0..=1.0, got {}",
+            p
+        );
+        self.pick = Pick::Probability(p);
+        self
+    }
+}
+
+impl<I, J> AlternatingRandom<I, J>
+where
+    I: iter::ExactSizeIterator,
+    J: iter::ExactSizeIterator<Item = I::Item>,
+{
+    /// Create a new `AlternatingRandom` iterator from two other iterables,
+    /// producing each of their interleavings with equal probability.
+    ///
+    /// Alternative to [`AlternatingExt::alternate_riffle`]. There is no difference.
+    pub fn riffle(
+        i: impl IntoIterator<IntoIter = I>,
+        j: impl IntoIterator<IntoIter = J>,
+        seed: u64,
+    ) -> Self {
+        Self {
+            pick: Pick::Remaining,
+            ..Self::new(i, j, seed)
+        }
+    }
+}
+
+impl<I, J> AlternatingRandom<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    fn pick(&mut self) -> Side {
+        let left = match self.pick {
+            Pick::Probability(p) => self.rng.next_f64() < p,
+            Pick::Remaining => {
+                // Only constructed for `ExactSizeIterator`s, so the lower bounds are exact.
+                let mut i_len = self.i.size_hint().0 as u64;
+                let mut j_len = self.j.size_hint().0 as u64;
+                if i_len.checked_add(j_len).is_none() {
+                    // Only the ratio matters, and lengths this large are never materialized.
+                    i_len /= 2;
+                    j_len /= 2;
+                }
+                match i_len + j_len {
+                    0 => true,
+                    total => self.rng.below(total) < i_len,
+                }
+            }
+        };
+        if left {
+            Side::Left
+        } else {
+            Side::Right
+        }
+    }
+}
+
+impl<I, J> Iterator for AlternatingRandom<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.only {
+            Some(Side::Left) => self.i.next(),
+            Some(Side::Right) => self.j.next(),
+            None => match self.pick() {
+                Side::Left => self.i.next().or_else(|| {
+                    self.only = Some(Side::Right);
+                    self.j.next()
+                }),
+                Side::Right => self.j.next().or_else(|| {
+                    self.only = Some(Side::Left);
+                    self.i.next()
+                }),
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (i_lower, i_upper) = self.i.size_hint();
+        let (j_lower, j_upper) = self.j.size_hint();
+        (
+            usize::saturating_add(i_lower, j_lower),
+            i_upper.and_then(|i| j_upper.and_then(|j| usize::checked_add(i, j))),
+        )
+    }
+}
+
+impl<I, J> iter::FusedIterator for AlternatingRandom<I, J>
+where
+    I: iter::FusedIterator,
+    J: iter::FusedIterator<Item = I::Item>,
+{
+}
+
+/// The SplitMix64 generator, chosen for being tiny and identical on every platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0.0..1.0`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `0..n`, without modulo bias. `n` must not be zero.
+    fn below(&mut self, n: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let a = 0..10;
+        let b = 10..20;
+
+        let first: Vec<_> = a.clone().alternate_random(b.clone(), 42).collect();
+        let second: Vec<_> = a.alternate_random(b, 42).collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn splitmix64_reference() {
+        let mut rng = SplitMix64(0);
+
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    }
+
+    #[test]
+    fn known_sequence() {
+        let iter = (0..4).alternate_random(10..14, 7);
+
+        assert_eq!(iter.collect::<Vec<_>>(), [0, 1, 10, 11, 2, 3, 12, 13]);
+    }
+
+    #[test]
+    fn order_preserving() {
+        for seed in 0..100 {
+            let merged: Vec<_> = (0..5).alternate_random(10..17, seed).collect();
+
+            let left: Vec<_> = merged.iter().copied().filter(|&n| n < 10).collect();
+            let right: Vec<_> = merged.iter().copied().filter(|&n| n >= 10).collect();
+            assert_eq!(left, [0, 1, 2, 3, 4]);
+            assert_eq!(right, [10, 11, 12, 13, 14, 15, 16]);
+        }
+    }
+
+    #[test]
+    fn certain_probability() {
+        let iter = (0..3).alternate_random(3..5, 0).left_probability(1.0);
+        assert_eq!(iter.collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+        let iter = (0..3).alternate_random(3..5, 0).left_probability(0.0);
+        assert_eq!(iter.collect::<Vec<_>>(), [3, 4, 0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_probability() {
+        let _ = (0..3).alternate_random(3..5, 0).left_probability(f64::NAN);
+    }
+
+    #[test]
+    fn riffle_uniform() {
+        // There are 6 ways to interleave two sequences of length 2.
+        let mut counts = std::collections::HashMap::new();
+        for seed in 0..6000 {
+            let merged: Vec<_> = (0..2).alternate_riffle(2..4, seed).collect();
+            *counts.entry(merged).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts.len(), 6);
+        for count in counts.values() {
+            assert!((800..1200).contains(count), "skewed counts: {:?}", counts);
+        }
+    }
+
+    #[test]
+    fn riffle_one_empty() {
+        let iter = (0..3).alternate_riffle(0..0, 1);
+
+        assert_eq!(iter.collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let iter = (0..3).alternate_random(3..5, 0);
+
+        assert_eq!(iter.size_hint(), (5, Some(5)));
+        assert_eq!(iter.count(), 5, "Inaccurate size hint");
+    }
+}
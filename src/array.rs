@@ -0,0 +1,190 @@
+use crate::utils::Rotation;
+use crate::Mode;
+
+/// Struct for taking turns between the items of any fixed number of iterators, stored inline.
+///
+/// The iterators take turns in the order of the array, one item at a time,
+/// and exhaustion is handled according to the [`Mode`]:
+///
+/// - [`Mode::Gapped`]: the turn of an exhausted iterator returns `None`, and the rotation continues.
+/// - [`Mode::All`]: exhausted iterators are skipped until every one of them is.
+/// - [`Mode::NoRemainder`]: iteration stops at the first exhausted iterator.
+///
+/// No allocation is needed, so this works without the `std` feature.
+///
+/// This struct is created by the [`alternate_array`] function, see its documentation for more.
+#[derive(Debug, Clone)]
+pub struct AlternatingArray<I, const N: usize> {
+    iters: [I; N],
+    rotation: Rotation<N>,
+}
+
+/// Takes an array of iterators and creates a new iterator over all of them in turn.
+///
+/// The first iterator of the array goes first, and exhaustion is handled in [`Mode::Gapped`]
+/// unless another [`mode`](AlternatingArray::mode) is set,
+/// the way [`alternate_with`](crate::AlternatingExt::alternate_with) would for two iterators.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{alternate_array, Mode};
+///
+/// let temperature = [20, 21, 22];
+/// let humidity = [40, 41];
+/// let pressure = [1000, 1001, 1002];
+///
+/// let mut readings = [0; 8];
+/// let iter = alternate_array([&temperature[..], &humidity, &pressure]).mode(Mode::All);
+/// for (slot, reading) in readings.iter_mut().zip(iter) {
+///     *slot = *reading;
+/// }
+///
+/// assert_eq!(readings, [20, 40, 1000, 21, 41, 1001, 22, 1002]);
+/// ```
+pub fn alternate_array<I, const N: usize>(iters: [I; N]) -> AlternatingArray<I::IntoIter, N>
+where
+    I: IntoIterator,
+{
+    AlternatingArray::new(iters)
+}
+
+impl<I, const N: usize> AlternatingArray<I, N>
+where
+    I: Iterator,
+{
+    /// Create a new `AlternatingArray` from an array of iterables.
+    ///
+    /// Alternative to [`alternate_array`]. There is no difference.
+    pub fn new(iters: [impl IntoIterator<IntoIter = I>; N]) -> Self {
+        Self {
+            iters: iters.map(IntoIterator::into_iter),
+            rotation: Rotation::new(N),
+        }
+    }
+}
+
+impl<I, const N: usize> AlternatingArray<I, N> {
+    /// Handle exhaustion according to the given [`Mode`], which is [`Mode::Gapped`] by default.
+    ///
+    /// Should be set before iterating, since iterators found exhausted so far are not remembered
+    /// in every mode.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.rotation.mode = mode;
+        self
+    }
+
+    /// The index of the iterator whose turn is next.
+    pub fn turn(&self) -> usize {
+        self.rotation.turn()
+    }
+
+    /// Unwraps this `AlternatingArray`, returning the underlying iterators.
+    pub fn into_inner(self) -> [I; N] {
+        self.iters
+    }
+}
+
+impl<I, const N: usize> Iterator for AlternatingArray<I, N>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let iters = &mut self.iters;
+        self.rotation.next(|k| iters[k].next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rotation.size_hint(|k| self.iters[k].size_hint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlternatingExt;
+
+    #[test]
+    fn modes() {
+        let make = || alternate_array([1..3, 10..14, 20..21]);
+
+        assert_eq!(
+            make().collect::<Vec<_>>(),
+            [1, 10, 20, 2, 11],
+            "gapped stops at the first None"
+        );
+        let mut gapped = make();
+        assert_eq!(gapped.by_ref().take(5).count(), 5);
+        assert_eq!(gapped.next(), None);
+        assert_eq!(gapped.next(), None);
+        assert_eq!(gapped.next(), Some(12));
+        assert_eq!(gapped.turn(), 2);
+
+        assert_eq!(
+            make().mode(Mode::All).collect::<Vec<_>>(),
+            [1, 10, 20, 2, 11, 12, 13]
+        );
+        let mut no_remainder = make().mode(Mode::NoRemainder);
+        assert_eq!(
+            no_remainder.by_ref().collect::<Vec<_>>(),
+            [1, 10, 20, 2, 11]
+        );
+        assert_eq!(no_remainder.next(), None);
+    }
+
+    #[test]
+    fn matches_two_way() {
+        for (a, b) in [(0..3, 10..13), (0..4, 10..12), (0..1, 10..15), (0..0, 0..0)] {
+            let array = alternate_array([a.clone(), b.clone()]);
+            let two_way = a.clone().alternate_with(b.clone());
+            assert!(array.take(20).eq(two_way.take(20)));
+
+            let array = alternate_array([a.clone(), b.clone()]).mode(Mode::All);
+            assert!(array.eq(a.clone().alternate_with_all(b.clone())));
+
+            let array = alternate_array([a.clone(), b.clone()]).mode(Mode::NoRemainder);
+            assert!(array.eq(a.alternate_with_no_remainder(b)));
+        }
+    }
+
+    #[test]
+    fn size_hints() {
+        for mode in [Mode::Gapped, Mode::All, Mode::NoRemainder] {
+            let mut iter = alternate_array([0..4, 10..12, 20..25, 30..34]).mode(mode);
+            loop {
+                let (lower, upper) = iter.size_hint();
+                let remaining = iter.clone().count();
+                assert_eq!((lower, upper), (remaining, Some(remaining)), "{:?}", mode);
+                if iter.next().is_none() {
+                    break;
+                }
+            }
+        }
+
+        let iter = alternate_array([0.., 10..]).mode(Mode::NoRemainder);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+        let iter = alternate_array([(0..).take(2), (0..).take(5)]).mode(Mode::All);
+        assert_eq!(iter.size_hint(), (7, Some(7)));
+    }
+
+    #[test]
+    fn no_iterators() {
+        let mut iter = alternate_array::<core::ops::Range<u8>, 0>([]);
+
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.mode(Mode::All).next(), None);
+    }
+
+    #[test]
+    fn into_inner() {
+        let mut iter = alternate_array([[1, 2].into_iter(), [3, 4].into_iter()]);
+        iter.next();
+
+        let [a, b] = iter.into_inner();
+        assert_eq!(a.as_slice(), [2]);
+        assert_eq!(b.as_slice(), [3, 4]);
+    }
+}
@@ -0,0 +1,523 @@
+//! Interleaves the records of files, or splits a file into several, from the command line.
+//!
+//! Requires the `cli` feature.
+
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process::ExitCode;
+
+use alternating_iter::Mode;
+
+const USAGE: &str = "\
+Usage: alternate [OPTIONS] FILE FILE...
+       alternate split [OPTIONS] INPUT OUTPUT OUTPUT...
+
+Interleaves the records of the files to standard output,
+or splits the records of INPUT between the outputs in turn.
+A FILE or INPUT of `-` reads standard input.
+
+Options:
+      --mode MODE       What to do once a file runs out, when interleaving:
+                          gap           write an empty record in its place
+                          all           continue with the other files (default)
+                          no-remainder  stop
+      --ratio M:N[:..]  Number of records per turn for each file (default: 1 each)
+      --start SIDE      Start with the first file, `left` (default), or the second, `right`
+  -z, --null            Records end with NUL instead of a newline
+  -h, --help            Print this help
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let stdout = io::stdout();
+    match run(&args, &mut stdout.lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::Help) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Err(error @ Error::Usage(_)) => {
+            eprintln!("alternate: {}\n\n{}", error, USAGE);
+            ExitCode::from(2)
+        }
+        Err(error) => {
+            eprintln!("alternate: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Error {
+    Help,
+    Usage(String),
+    Io(String, io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Help => f.write_str("help requested"),
+            Self::Usage(message) => f.write_str(message),
+            Self::Io(path, error) => write!(f, "{}: {}", path, error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    split: bool,
+    mode: Mode,
+    ratio: Option<Vec<usize>>,
+    start_right: bool,
+    delimiter: u8,
+    paths: Vec<String>,
+}
+
+fn parse(args: &[String]) -> Result<Options, Error> {
+    let mut options = Options {
+        split: false,
+        mode: Mode::All,
+        ratio: None,
+        start_right: false,
+        delimiter: b'\n',
+        paths: Vec::new(),
+    };
+    let mut args = args.iter();
+    let mut first = true;
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| Error::Usage(format!("{} needs a value", flag)))
+        };
+        match flag {
+            "split" if first => options.split = true,
+            "-h" | "--help" => return Err(Error::Help),
+            "-z" | "--null" => options.delimiter = 0,
+            "--mode" => {
+                options.mode = match value()?.as_str() {
+                    "gap" => Mode::Gapped,
+                    "all" => Mode::All,
+                    "no-remainder" => Mode::NoRemainder,
+                    other => return Err(Error::Usage(format!("unknown mode `{}`", other))),
+                }
+            }
+            "--ratio" => {
+                let ratio = value()?
+                    .split(':')
+                    .map(|count| count.parse().ok().filter(|&count| count > 0))
+                    .collect::<Option<Vec<usize>>>()
+                    .ok_or_else(|| {
+                        Error::Usage("ratio must be positive numbers like 2:1".into())
+                    })?;
+                options.ratio = Some(ratio);
+            }
+            "--start" => {
+                options.start_right = match value()?.as_str() {
+                    "left" => false,
+                    "right" => true,
+                    other => return Err(Error::Usage(format!("unknown side `{}`", other))),
+                }
+            }
+            "-" => options.paths.push(arg.clone()),
+            _ if arg.starts_with('-') => {
+                return Err(Error::Usage(format!("unknown option `{}`", arg)))
+            }
+            _ => options.paths.push(arg.clone()),
+        }
+        first = false;
+    }
+    Ok(options)
+}
+
+fn run(args: &[String], stdout: &mut dyn Write) -> Result<(), Error> {
+    let mut options = parse(args)?;
+    let (input, mut paths) = if options.split {
+        if options.paths.len() < 3 {
+            return Err(Error::Usage(
+                "split needs an input and at least two outputs".into(),
+            ));
+        }
+        (Some(options.paths.remove(0)), options.paths)
+    } else {
+        if options.paths.len() < 2 {
+            return Err(Error::Usage("needs at least two files".into()));
+        }
+        if options.paths.iter().filter(|path| *path == "-").count() > 1 {
+            return Err(Error::Usage("standard input can only be read once".into()));
+        }
+        (None, options.paths)
+    };
+    let mut ratio = options.ratio.unwrap_or_else(|| vec![1; paths.len()]);
+    if ratio.len() != paths.len() {
+        return Err(Error::Usage(format!(
+            "ratio has {} parts for {} files",
+            ratio.len(),
+            paths.len()
+        )));
+    }
+    if options.start_right {
+        paths.rotate_left(1);
+        ratio.rotate_left(1);
+    }
+
+    match input {
+        Some(input) => {
+            let records = Records::open(&input, options.delimiter)?;
+            let mut outputs = paths
+                .iter()
+                .map(|path| {
+                    File::create(path)
+                        .map(BufWriter::new)
+                        .map_err(|error| Error::Io(path.clone(), error))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            split(records, &mut outputs, &paths, &ratio, options.delimiter)?;
+            for (output, path) in outputs.iter_mut().zip(&paths) {
+                output
+                    .flush()
+                    .map_err(|error| Error::Io(path.clone(), error))?;
+            }
+            Ok(())
+        }
+        None => {
+            let sources = paths
+                .iter()
+                .zip(&ratio)
+                .map(|(path, &count)| {
+                    Records::open(path, options.delimiter).map(|records| Turns { records, count })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut out = BufWriter::new(stdout);
+            interleave(sources, &ratio, options.mode, options.delimiter, &mut out)?;
+            out.flush().map_err(stdout_error)
+        }
+    }
+}
+
+fn stdout_error(error: io::Error) -> Error {
+    Error::Io("standard output".into(), error)
+}
+
+/// The records of a file, naming it in errors.
+struct Records {
+    reader: Box<dyn BufRead>,
+    path: String,
+    delimiter: u8,
+}
+
+impl Records {
+    fn open(path: &str, delimiter: u8) -> Result<Self, Error> {
+        let reader: Box<dyn BufRead> = if path == "-" {
+            Box::new(io::stdin().lock())
+        } else {
+            File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|error| Error::Io(path.to_string(), error))?
+        };
+        Ok(Self {
+            reader,
+            path: path.to_string(),
+            delimiter,
+        })
+    }
+}
+
+impl Iterator for Records {
+    type Item = Result<Vec<u8>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = Vec::new();
+        match self.reader.read_until(self.delimiter, &mut record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(record)),
+            Err(error) => Some(Err(Error::Io(self.path.clone(), error))),
+        }
+    }
+}
+
+/// The records of a file, up to `count` per turn, ending once there are none left.
+struct Turns {
+    records: Records,
+    count: usize,
+}
+
+impl Iterator for Turns {
+    type Item = Result<Vec<Vec<u8>>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let turn = (&mut self.records)
+            .take(self.count)
+            .collect::<Result<Vec<_>, _>>();
+        match turn {
+            Ok(records) if records.is_empty() => None,
+            turn => Some(turn),
+        }
+    }
+}
+
+/// Writes the turns of the sources in rotation, handling exhausted sources
+/// like [`alternate_array`](alternating_iter::alternate_array) with `mode`.
+///
+/// A turn that comes up short of `ratio[k]` records is handled like an exhausted source:
+/// gap mode writes an empty record for each missing one, and no-remainder mode stops.
+fn interleave(
+    mut sources: Vec<Turns>,
+    ratio: &[usize],
+    mode: Mode,
+    delimiter: u8,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let mut exhausted = vec![false; sources.len()];
+    // Gaps are only written once a record follows, so that none trail the output.
+    let mut gaps = 0;
+    for k in (0..sources.len()).cycle() {
+        if exhausted.iter().all(|&exhausted| exhausted) {
+            break;
+        }
+        let turn = if exhausted[k] {
+            None
+        } else {
+            sources[k].next()
+        };
+        let records = match turn {
+            Some(turn) => turn?,
+            None => {
+                exhausted[k] = true;
+                Vec::new()
+            }
+        };
+        if !records.is_empty() {
+            write_turn(out, gaps, &records, delimiter).map_err(stdout_error)?;
+            gaps = 0;
+        }
+        let missing = ratio[k] - records.len();
+        if missing > 0 {
+            match mode {
+                Mode::Gapped => gaps += missing,
+                Mode::All => {}
+                Mode::NoRemainder => break,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes `gaps` empty records followed by `records`.
+fn write_turn(
+    out: &mut dyn Write,
+    gaps: usize,
+    records: &[Vec<u8>],
+    delimiter: u8,
+) -> io::Result<()> {
+    for _ in 0..gaps {
+        out.write_all(&[delimiter])?;
+    }
+    for record in records {
+        write_record(out, record, delimiter)?;
+    }
+    Ok(())
+}
+
+/// Writes the records of `input` to `ratio[k]` at a time to the `k`-th output.
+fn split<W: Write>(
+    records: Records,
+    outputs: &mut [W],
+    paths: &[String],
+    ratio: &[usize],
+    delimiter: u8,
+) -> Result<(), Error> {
+    let turns = ratio
+        .iter()
+        .enumerate()
+        .flat_map(|(k, &count)| (0..count).map(move |_| k))
+        .cycle();
+    for (record, k) in records.zip(turns) {
+        write_record(&mut outputs[k], &record?, delimiter)
+            .map_err(|error| Error::Io(paths[k].clone(), error))?;
+    }
+    Ok(())
+}
+
+/// Writes `record`, adding the delimiter the last record of a file may lack.
+fn write_record(out: &mut dyn Write, record: &[u8], delimiter: u8) -> io::Result<()> {
+    out.write_all(record)?;
+    if record.last() != Some(&delimiter) {
+        out.write_all(&[delimiter])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn file(dir: &TempDir, name: &str, contents: &str) -> String {
+        let path = dir.path().join(name);
+        fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    fn alternate(args: &[&str]) -> Result<String, Error> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn modes() {
+        let dir = TempDir::new().unwrap();
+        let a = file(&dir, "a", "a1\na2\na3");
+        let b = file(&dir, "b", "b1\n");
+
+        assert_eq!(alternate(&[&a, &b]).unwrap(), "a1\nb1\na2\na3\n");
+        assert_eq!(
+            alternate(&["--mode", "no-remainder", &a, &b]).unwrap(),
+            "a1\nb1\na2\n"
+        );
+        assert_eq!(
+            alternate(&["--mode=gap", &a, &b]).unwrap(),
+            "a1\nb1\na2\n\na3\n"
+        );
+        assert_eq!(
+            alternate(&["--mode", "gap", &b, &a]).unwrap(),
+            "b1\na1\n\na2\n\na3\n"
+        );
+    }
+
+    #[test]
+    fn ratio_and_start() {
+        let dir = TempDir::new().unwrap();
+        let a = file(&dir, "a", "a1\na2\na3\na4\n");
+        let b = file(&dir, "b", "b1\nb2\n");
+        let c = file(&dir, "c", "c1\n");
+
+        assert_eq!(
+            alternate(&["--ratio", "2:1", &a, &b]).unwrap(),
+            "a1\na2\nb1\na3\na4\nb2\n"
+        );
+        assert_eq!(
+            alternate(&["--start", "right", "--ratio", "2:1", &a, &b]).unwrap(),
+            "b1\na1\na2\nb2\na3\na4\n"
+        );
+        assert_eq!(
+            alternate(&[&a, &b, &c]).unwrap(),
+            "a1\nb1\nc1\na2\nb2\na3\na4\n"
+        );
+        // `c` comes up short on its turn of two.
+        assert_eq!(
+            alternate(&["--mode", "no-remainder", "--ratio", "1:1:2", &a, &b, &c]).unwrap(),
+            "a1\nb1\nc1\n"
+        );
+        assert_eq!(
+            alternate(&["--mode", "gap", "--ratio", "1:1:2", &a, &b, &c]).unwrap(),
+            "a1\nb1\nc1\n\na2\nb2\n\n\na3\n\n\n\na4\n"
+        );
+    }
+
+    #[test]
+    fn many_files() {
+        let dir = TempDir::new().unwrap();
+        let paths: Vec<String> = (0..10)
+            .map(|k| file(&dir, &k.to_string(), &format!("{}a\n{}b\n", k, k)))
+            .collect();
+        let args: Vec<&str> = paths.iter().map(String::as_str).collect();
+
+        let expected: String = ["a", "b"]
+            .iter()
+            .flat_map(|record| (0..10).map(move |k| format!("{}{}\n", k, record)))
+            .collect();
+        assert_eq!(alternate(&args).unwrap(), expected);
+    }
+
+    #[test]
+    fn null_records() {
+        let dir = TempDir::new().unwrap();
+        let a = file(&dir, "a", "one\nline\0two\0");
+        let b = file(&dir, "b", "x\0");
+
+        assert_eq!(alternate(&["-z", &a, &b]).unwrap(), "one\nline\0x\0two\0");
+    }
+
+    #[test]
+    fn split_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let input = file(&dir, "input", "1\n2\n3\n4\n5\n6\n7\n");
+        let outputs: Vec<String> = ["x", "y", "z"]
+            .iter()
+            .map(|name| dir.path().join(name).to_str().unwrap().to_string())
+            .collect();
+
+        alternate(&[
+            "split",
+            "--ratio",
+            "1:2:1",
+            &input,
+            &outputs[0],
+            &outputs[1],
+            &outputs[2],
+        ])
+        .unwrap();
+
+        let read = |path: &str| fs::read_to_string(Path::new(path)).unwrap();
+        assert_eq!(read(&outputs[0]), "1\n5\n");
+        assert_eq!(read(&outputs[1]), "2\n3\n6\n7\n");
+        assert_eq!(read(&outputs[2]), "4\n");
+
+        let merged = alternate(&["--ratio", "1:2:1", &outputs[0], &outputs[1], &outputs[2]]);
+        assert_eq!(merged.unwrap(), read(&input));
+    }
+
+    #[test]
+    fn usage_errors() {
+        let dir = TempDir::new().unwrap();
+        let a = file(&dir, "a", "a\n");
+
+        assert!(matches!(alternate(&[&a]), Err(Error::Usage(_))));
+        assert!(matches!(
+            alternate(&["--mode", "some", &a, &a]),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(
+            alternate(&["--ratio", "1:0", &a, &a]),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(
+            alternate(&["--ratio", "1:1:1", &a, &a]),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(alternate(&["--ratio"]), Err(Error::Usage(_))));
+        assert!(matches!(
+            alternate(&["--bogus", &a, &a]),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(
+            alternate(&["split", &a, &a]),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(alternate(&["-", &a, "-"]), Err(Error::Usage(_))));
+        assert!(matches!(alternate(&["--help"]), Err(Error::Help)));
+
+        let missing = dir.path().join("missing");
+        let error = alternate(&[&a, missing.to_str().unwrap()]).unwrap_err();
+        assert!(matches!(error, Error::Io(..)));
+        assert!(error.to_string().contains("missing"));
+
+        // A directory opens, but fails once read.
+        let error = alternate(&[&a, dir.path().to_str().unwrap()]).unwrap_err();
+        assert!(matches!(error, Error::Io(..)));
+        assert!(error.to_string().starts_with(dir.path().to_str().unwrap()));
+    }
+}
@@ -0,0 +1,347 @@
+mod private {
+    pub trait Sealed {}
+}
+
+/// Unsigned integers whose bits [`interleave_bits`] and [`deinterleave_bits`] can alternate.
+///
+/// This trait is sealed and implemented for `u8`, `u16`, `u32` and `u64`.
+pub trait BitInterleave: Copy + private::Sealed {
+    /// The integer twice as wide, which holds the bits of two integers.
+    type Wide: Copy;
+
+    /// See [`interleave_bits`].
+    fn interleave(x: Self, y: Self) -> Self::Wide;
+
+    /// See [`deinterleave_bits`].
+    fn deinterleave(code: Self::Wide) -> (Self, Self);
+}
+
+/// Unsigned integers whose bits [`interleave_bits3`] and [`deinterleave_bits3`] can alternate.
+///
+/// This trait is sealed and implemented for `u8`, `u16` and `u32`.
+pub trait BitInterleave3: Copy + private::Sealed {
+    /// The smallest integer at least three times as wide, which holds the bits of three integers.
+    type Wide: Copy;
+
+    /// See [`interleave_bits3`].
+    fn interleave3(x: Self, y: Self, z: Self) -> Self::Wide;
+
+    /// See [`deinterleave_bits3`].
+    fn deinterleave3(code: Self::Wide) -> (Self, Self, Self);
+}
+
+/// Alternates the bits of two integers, starting with the lowest bit of `x`,
+/// giving their Morton code, or Z-order curve index.
+///
+/// This is [`alternate_with`](crate::AlternatingExt::alternate_with) applied to the bits,
+/// from least to most significant.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::interleave_bits;
+///
+/// assert_eq!(interleave_bits(0b11u32, 0b00), 0b0101u64);
+/// assert_eq!(interleave_bits(0b00u32, 0b11), 0b1010u64);
+/// assert_eq!(interleave_bits(u8::MAX, u8::MAX), u16::MAX);
+/// ```
+pub fn interleave_bits<T: BitInterleave>(x: T, y: T) -> T::Wide {
+    T::interleave(x, y)
+}
+
+/// Splits a Morton code into the two integers whose bits it alternates.
+///
+/// Inverse of [`interleave_bits`].
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::deinterleave_bits;
+///
+/// assert_eq!(deinterleave_bits::<u32>(0b0110), (0b10, 0b01));
+/// ```
+pub fn deinterleave_bits<T: BitInterleave>(code: T::Wide) -> (T, T) {
+    T::deinterleave(code)
+}
+
+/// Alternates the bits of three integers, starting with the lowest bit of `x`, then `y`, then `z`,
+/// giving their three-dimensional Morton code.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::interleave_bits3;
+///
+/// assert_eq!(interleave_bits3(0b11u8, 0b00, 0b01), 0b001_101u32);
+/// ```
+pub fn interleave_bits3<T: BitInterleave3>(x: T, y: T, z: T) -> T::Wide {
+    T::interleave3(x, y, z)
+}
+
+/// Splits a three-dimensional Morton code into the three integers whose bits it alternates.
+///
+/// Inverse of [`interleave_bits3`]. Bits above the three integers' combined width are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::deinterleave_bits3;
+///
+/// assert_eq!(deinterleave_bits3::<u8>(0b001_101), (0b11, 0b00, 0b01));
+/// ```
+pub fn deinterleave_bits3<T: BitInterleave3>(code: T::Wide) -> (T, T, T) {
+    T::deinterleave3(code)
+}
+
+macro_rules! impl_bit_interleave {
+    ($($ty:ty => $wide:ty),*) => {$(
+        impl private::Sealed for $ty {}
+
+        impl BitInterleave for $ty {
+            type Wide = $wide;
+
+            fn interleave(x: Self, y: Self) -> Self::Wide {
+                (spread2(x as u64) | spread2(y as u64) << 1) as $wide
+            }
+
+            fn deinterleave(code: Self::Wide) -> (Self, Self) {
+                (compact2(code as u64) as $ty, compact2((code >> 1) as u64) as $ty)
+            }
+        }
+    )*};
+}
+impl_bit_interleave!(u8 => u16, u16 => u32, u32 => u64);
+
+impl private::Sealed for u64 {}
+
+impl BitInterleave for u64 {
+    type Wide = u128;
+
+    fn interleave(x: Self, y: Self) -> Self::Wide {
+        let low = u32::interleave(x as u32, y as u32) as u128;
+        let high = u32::interleave((x >> 32) as u32, (y >> 32) as u32) as u128;
+        high << 64 | low
+    }
+
+    fn deinterleave(code: Self::Wide) -> (Self, Self) {
+        let (low_x, low_y) = u32::deinterleave(code as u64);
+        let (high_x, high_y) = u32::deinterleave((code >> 64) as u64);
+        (
+            (high_x as u64) << 32 | low_x as u64,
+            (high_y as u64) << 32 | low_y as u64,
+        )
+    }
+}
+
+macro_rules! impl_bit_interleave3 {
+    ($($ty:ty => $wide:ty),*) => {$(
+        impl BitInterleave3 for $ty {
+            type Wide = $wide;
+
+            fn interleave3(x: Self, y: Self, z: Self) -> Self::Wide {
+                (spread3(x as u64) | spread3(y as u64) << 1 | spread3(z as u64) << 2) as $wide
+            }
+
+            fn deinterleave3(code: Self::Wide) -> (Self, Self, Self) {
+                let code = code as u64;
+                (
+                    compact3(code) as $ty,
+                    compact3(code >> 1) as $ty,
+                    compact3(code >> 2) as $ty,
+                )
+            }
+        }
+    )*};
+}
+impl_bit_interleave3!(u8 => u32, u16 => u64);
+
+impl BitInterleave3 for u32 {
+    type Wide = u128;
+
+    fn interleave3(x: Self, y: Self, z: Self) -> Self::Wide {
+        let low = u16::interleave3(x as u16, y as u16, z as u16) as u128;
+        let high = u16::interleave3((x >> 16) as u16, (y >> 16) as u16, (z >> 16) as u16) as u128;
+        high << 48 | low
+    }
+
+    fn deinterleave3(code: Self::Wide) -> (Self, Self, Self) {
+        let (low_x, low_y, low_z) = u16::deinterleave3(code as u64);
+        let (high_x, high_y, high_z) = u16::deinterleave3((code >> 48) as u64);
+        (
+            (high_x as u32) << 16 | low_x as u32,
+            (high_y as u32) << 16 | low_y as u32,
+            (high_z as u32) << 16 | low_z as u32,
+        )
+    }
+}
+
+/// Every other bit, starting with the lowest.
+const EVERY_2ND: u64 = 0x5555_5555_5555_5555;
+/// Every third bit, starting with the lowest.
+const EVERY_3RD: u64 = 0x1249_2492_4924_9249;
+
+// With BMI2, scattering bits to a mask and gathering them back are single instructions.
+// They are only used when enabled at compile time, since they are slow on some older AMD processors.
+
+/// Moves the low 32 bits of `x` to the even bits.
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+fn spread2(x: u64) -> u64 {
+    // SAFETY: BMI2 is enabled at compile time.
+    unsafe { core::arch::x86_64::_pdep_u64(x, EVERY_2ND) }
+}
+
+/// Moves the even bits of `x` to the low 32 bits.
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+fn compact2(x: u64) -> u64 {
+    // SAFETY: BMI2 is enabled at compile time.
+    unsafe { core::arch::x86_64::_pext_u64(x, EVERY_2ND) }
+}
+
+/// Moves the low 21 bits of `x` to every third bit.
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+fn spread3(x: u64) -> u64 {
+    // SAFETY: BMI2 is enabled at compile time.
+    unsafe { core::arch::x86_64::_pdep_u64(x, EVERY_3RD) }
+}
+
+/// Moves every third bit of `x` to the low 21 bits.
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+fn compact3(x: u64) -> u64 {
+    // SAFETY: BMI2 is enabled at compile time.
+    unsafe { core::arch::x86_64::_pext_u64(x, EVERY_3RD) }
+}
+
+// Otherwise, the bits are moved in groups, halving the group size at every step.
+
+/// Moves the low 32 bits of `x` to the even bits.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+fn spread2(x: u64) -> u64 {
+    let mut x = x & 0x0000_0000_FFFF_FFFF;
+    x = (x | x << 16) & 0x0000_FFFF_0000_FFFF;
+    x = (x | x << 8) & 0x00FF_00FF_00FF_00FF;
+    x = (x | x << 4) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | x << 2) & 0x3333_3333_3333_3333;
+    (x | x << 1) & EVERY_2ND
+}
+
+/// Moves the even bits of `x` to the low 32 bits.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+fn compact2(x: u64) -> u64 {
+    let mut x = x & EVERY_2ND;
+    x = (x | x >> 1) & 0x3333_3333_3333_3333;
+    x = (x | x >> 2) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | x >> 4) & 0x00FF_00FF_00FF_00FF;
+    x = (x | x >> 8) & 0x0000_FFFF_0000_FFFF;
+    (x | x >> 16) & 0x0000_0000_FFFF_FFFF
+}
+
+/// Moves the low 21 bits of `x` to every third bit.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+fn spread3(x: u64) -> u64 {
+    let mut x = x & 0x001F_FFFF;
+    x = (x | x << 32) & 0x001F_0000_0000_FFFF;
+    x = (x | x << 16) & 0x001F_0000_FF00_00FF;
+    x = (x | x << 8) & 0x100F_00F0_0F00_F00F;
+    x = (x | x << 4) & 0x10C3_0C30_C30C_30C3;
+    (x | x << 2) & EVERY_3RD
+}
+
+/// Moves every third bit of `x` to the low 21 bits.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+fn compact3(x: u64) -> u64 {
+    let mut x = x & EVERY_3RD;
+    x = (x | x >> 2) & 0x10C3_0C30_C30C_30C3;
+    x = (x | x >> 4) & 0x100F_00F0_0F00_F00F;
+    x = (x | x >> 8) & 0x001F_0000_FF00_00FF;
+    x = (x | x >> 16) & 0x001F_0000_0000_FFFF;
+    (x | x >> 32) & 0x001F_FFFF
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlternatingExt;
+
+    /// One bit at a time, from least to most significant.
+    fn bits(x: u128, width: u32) -> impl Iterator<Item = bool> {
+        (0..width).map(move |bit| x >> bit & 1 == 1)
+    }
+
+    #[test]
+    fn matches_alternating() {
+        for x in 0..=u8::MAX {
+            for y in 0..=u8::MAX {
+                let code = interleave_bits(x, y);
+
+                assert!(bits(code as u128, 16)
+                    .eq(bits(x as u128, 8).alternate_with_all(bits(y as u128, 8))));
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_exhaustive() {
+        for code in 0..=u16::MAX {
+            let (x, y) = deinterleave_bits::<u8>(code);
+            assert_eq!(interleave_bits(x, y), code);
+        }
+        for code in 0..1u32 << 24 {
+            let (x, y, z) = deinterleave_bits3::<u8>(code);
+            assert_eq!(interleave_bits3(x, y, z), code);
+        }
+    }
+
+    #[test]
+    fn three_way_order() {
+        let code = interleave_bits3(0b1010u8, 0b1100, 0b1111);
+
+        let x = bits(0b1010, 4);
+        let y = bits(0b1100, 4);
+        let z = bits(0b1111, 4);
+        let expected = x.zip(y).zip(z).flat_map(|((x, y), z)| [x, y, z]);
+        assert!(bits(code as u128, 12).eq(expected));
+    }
+
+    #[test]
+    fn wide() {
+        let samples = [0, 1, 0xDEAD_BEEF_0123_4567, 0x8000_0000_0000_0001, u64::MAX];
+        for &x in &samples {
+            for &y in &samples {
+                let code = interleave_bits(x, y);
+                assert!(
+                    bits(code, 128).eq(bits(x as u128, 64).alternate_with_all(bits(y as u128, 64)))
+                );
+                assert_eq!(deinterleave_bits::<u64>(code), (x, y));
+
+                let (x, y) = (x as u16, y as u16);
+                assert_eq!(deinterleave_bits::<u16>(interleave_bits(x, y)), (x, y));
+
+                let (x, y) = (x as u32, y as u32);
+                assert_eq!(deinterleave_bits::<u32>(interleave_bits(x, y)), (x, y));
+                let (x, y, z) = (x, y, x ^ y);
+                assert_eq!(
+                    deinterleave_bits3::<u32>(interleave_bits3(x, y, z)),
+                    (x, y, z)
+                );
+                assert_eq!(
+                    interleave_bits3(x, y, z) as u64 & 0xFFFF_FFFF_FFFF,
+                    interleave_bits3(x as u16, y as u16, z as u16),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        assert_eq!(interleave_bits(u32::MAX, 0), EVERY_2ND);
+        assert_eq!(interleave_bits(0, u32::MAX), EVERY_2ND << 1);
+        assert_eq!(
+            interleave_bits3(u16::MAX, 0, 0),
+            EVERY_3RD & 0xFFFF_FFFF_FFFF
+        );
+        assert_eq!(
+            interleave_bits3(u32::MAX, u32::MAX, u32::MAX),
+            u128::MAX >> 32
+        );
+    }
+}
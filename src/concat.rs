@@ -0,0 +1,145 @@
+use core::fmt;
+
+#[allow(unused_imports)]
+use crate::AlternatingExt;
+#[cfg(feature = "std")]
+use crate::Either2;
+
+/// Concatenates the pieces of `a` and `b` in the order of [`AlternatingExt::alternate_with_all`],
+/// with `separator` between every two pieces.
+///
+/// The pieces are measured on clones of the iterators first, so that the result is allocated once.
+#[cfg(feature = "std")]
+pub(crate) fn join<A, B>(a: A, b: B, separator: &str) -> String
+where
+    A: Iterator + Clone,
+    A::Item: AsRef<str>,
+    B: Iterator + Clone,
+    B::Item: AsRef<str>,
+{
+    let (a_pieces, a_len) = a.clone().fold((0usize, 0usize), |(pieces, len), piece| {
+        (pieces + 1, len + piece.as_ref().len())
+    });
+    let (b_pieces, b_len) = b.clone().fold((0usize, 0usize), |(pieces, len), piece| {
+        (pieces + 1, len + piece.as_ref().len())
+    });
+    let capacity = a_len + b_len + separator.len() * (a_pieces + b_pieces).saturating_sub(1);
+
+    let mut joined = String::with_capacity(capacity);
+    for (n, piece) in crate::alternate!(all: a, b).enumerate() {
+        if n > 0 {
+            joined.push_str(separator);
+        }
+        match &piece {
+            Either2::One(piece) => joined.push_str(piece.as_ref()),
+            Either2::Two(piece) => joined.push_str(piece.as_ref()),
+        }
+    }
+    joined
+}
+
+/// Struct for formatting the items of two iterators in an alternating fashion, without allocating.
+///
+/// The items are formatted in the order of [`AlternatingAll`](crate::AlternatingAll),
+/// each with the formatting options given to this struct, such as width.
+/// The iterators are cloned every time this struct is formatted.
+///
+/// This struct is created by the [`AlternatingExt::alternate_display`] method, see its documentation for more.
+#[derive(Debug, Clone)]
+pub struct AlternatingDisplay<I, J> {
+    i: I,
+    j: J,
+}
+
+impl<I, J> AlternatingDisplay<I, J>
+where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+{
+    /// Create a new `AlternatingDisplay` from two other iterables.
+    ///
+    /// Alternative to [`AlternatingExt::alternate_display`]. There is no difference.
+    pub fn new(i: impl IntoIterator<IntoIter = I>, j: impl IntoIterator<IntoIter = J>) -> Self {
+        Self {
+            i: i.into_iter(),
+            j: j.into_iter(),
+        }
+    }
+}
+
+impl<I, J> fmt::Display for AlternatingDisplay<I, J>
+where
+    I: Iterator + Clone,
+    I::Item: fmt::Display,
+    J: Iterator + Clone,
+    J::Item: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (mut i, mut j) = (self.i.clone(), self.j.clone());
+        loop {
+            match i.next() {
+                Some(item) => item.fmt(f)?,
+                None => return j.try_for_each(|item| item.fmt(f)),
+            }
+            match j.next() {
+                Some(item) => item.fmt(f)?,
+                None => return i.try_for_each(|item| item.fmt(f)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn template() {
+        let fragments = ["Hello, ", "! You have ", " new messages."];
+        let values = ["Alice".to_string(), 3.to_string()];
+
+        let rendered = fragments.iter().alternate_concat(&values);
+
+        assert_eq!(rendered, "Hello, Alice! You have 3 new messages.");
+        assert_eq!(rendered.capacity(), rendered.len());
+        assert_eq!(
+            fragments
+                .iter()
+                .alternate_display([&"Alice" as &dyn fmt::Display, &3])
+                .to_string(),
+            rendered
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn join_separators() {
+        assert_eq!(
+            ["a", "b", "c"].iter().alternate_join(["1"], ", "),
+            "a, 1, b, c"
+        );
+        assert_eq!(["a"].iter().alternate_join([] as [&str; 0], ", "), "a");
+        assert_eq!(
+            core::iter::empty::<&str>().alternate_join(core::iter::empty::<String>(), "-"),
+            ""
+        );
+
+        // Lengths are only known after iterating, which is done on a clone first.
+        let joined = (0..4)
+            .filter(|n| n % 2 == 0)
+            .map(|n| n.to_string())
+            .alternate_join(["x", "y", "z"], "/");
+        assert_eq!(joined, "0/x/2/y/z");
+        assert_eq!(joined.capacity(), joined.len());
+    }
+
+    #[test]
+    fn display_reusable() {
+        let display = (1..4).alternate_display(["a", "b"]);
+
+        assert_eq!(display.to_string(), "1a2b3");
+        assert_eq!(display.to_string(), "1a2b3");
+        assert_eq!(format!("{:>2}", display), " 1 a 2 b 3");
+    }
+}
@@ -0,0 +1,481 @@
+use crate::{AlternatingExt, Mode, Side};
+
+/// Creates an iterator over every way to interleave two slices while keeping the order within each.
+///
+/// The interleavings are produced in lexicographic order of their schedules,
+/// with [`Side::Left`] before [`Side::Right`]: first all of `a` then all of `b`,
+/// and last all of `b` then all of `a`.
+/// Their number is given by [`interleaving_count`].
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::interleavings;
+///
+/// let a = [1, 2];
+/// let b = [3];
+///
+/// let mut iter = interleavings(&a, &b);
+///
+/// assert_eq!(iter.next(), Some(vec![1, 2, 3]));
+/// assert_eq!(iter.next(), Some(vec![1, 3, 2]));
+/// assert_eq!(iter.next(), Some(vec![3, 1, 2]));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub fn interleavings<'a, T: Clone>(a: &'a [T], b: &'a [T]) -> Interleavings<'a, T> {
+    Interleavings {
+        a,
+        b,
+        schedule: schedule_at_start(a.len(), b.len()),
+        index: Some(0),
+        total: interleaving_count(a.len(), b.len()),
+        done: false,
+    }
+}
+
+/// Iterator over every interleaving of two slices.
+///
+/// This struct is created by the [`interleavings`] function, see its documentation for more.
+#[derive(Debug, Clone)]
+pub struct Interleavings<'a, T> {
+    a: &'a [T],
+    b: &'a [T],
+    /// `false` for left and `true` for right, so that the order of `bool` is the order of the schedules.
+    schedule: Vec<bool>,
+    /// Position of `schedule`, or `None` once past `usize::MAX`, which only happens if `total` is `None`.
+    index: Option<usize>,
+    total: Option<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Interleavings<'_, T> {
+    fn current(&self) -> Vec<T> {
+        let mut a = self.a.iter();
+        let mut b = self.b.iter();
+        self.schedule
+            .iter()
+            .map(|&right| if right { b.next() } else { a.next() })
+            .map(|item| item.expect("schedule matches the lengths").clone())
+            .collect()
+    }
+}
+
+impl<T: Clone> Iterator for Interleavings<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = self.current();
+        self.done = !next_schedule(&mut self.schedule);
+        self.index = self.index.and_then(|index| index.checked_add(1));
+        Some(item)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.index.and_then(|index| index.checked_add(n)) {
+            Some(index) => match interleaving_unrank(self.a.len(), self.b.len(), index) {
+                Some(schedule) => {
+                    self.schedule = schedule.into_iter().map(|s| s == Side::Right).collect();
+                    self.index = Some(index);
+                }
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            },
+            // Positions past `usize::MAX` cannot be unranked, so step through them instead.
+            None => {
+                for _ in 0..n {
+                    if !next_schedule(&mut self.schedule) {
+                        self.done = true;
+                        return None;
+                    }
+                }
+                self.index = None;
+            }
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match (self.total, self.index) {
+            (Some(total), Some(index)) => (total - index, Some(total - index)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T: Clone> core::iter::FusedIterator for Interleavings<'_, T> {}
+
+/// Number of ways to interleave sequences of the given lengths, or `None` if it overflows `usize`.
+///
+/// This is the binomial coefficient `(left_len + right_len) choose left_len`.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::interleaving_count;
+///
+/// assert_eq!(interleaving_count(2, 1), Some(3));
+/// assert_eq!(interleaving_count(3, 3), Some(20));
+/// assert_eq!(interleaving_count(0, 5), Some(1));
+/// assert_eq!(interleaving_count(100, 100), None);
+/// ```
+pub fn interleaving_count(left_len: usize, right_len: usize) -> Option<usize> {
+    binomial(left_len.checked_add(right_len)?, left_len.min(right_len))
+}
+
+/// Position of the interleaving described by `schedule` in the order of [`interleavings`],
+/// or `None` if it overflows `usize`.
+///
+/// Inverse of [`interleaving_unrank`].
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{interleaving_rank, Side::{Left, Right}};
+///
+/// assert_eq!(interleaving_rank(&[Left, Left, Right]), Some(0));
+/// assert_eq!(interleaving_rank(&[Left, Right, Left]), Some(1));
+/// assert_eq!(interleaving_rank(&[Right, Left, Left]), Some(2));
+/// ```
+pub fn interleaving_rank(schedule: &[Side]) -> Option<usize> {
+    let mut left = schedule.iter().filter(|&&s| s == Side::Left).count();
+    let mut right = schedule.len() - left;
+    let mut rank = 0usize;
+    for side in schedule {
+        match side {
+            Side::Left => left -= 1,
+            Side::Right => {
+                // Skip every interleaving that takes from the left here instead.
+                if left > 0 {
+                    rank = rank.checked_add(binomial(left - 1 + right, left - 1)?)?;
+                }
+                right -= 1;
+            }
+        }
+    }
+    Some(rank)
+}
+
+/// Schedule of the interleaving at position `index` in the order of [`interleavings`],
+/// or `None` if `index` is out of range.
+///
+/// Inverse of [`interleaving_rank`].
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{interleaving_unrank, Side::{Left, Right}};
+///
+/// assert_eq!(interleaving_unrank(2, 1, 1), Some(vec![Left, Right, Left]));
+/// assert_eq!(interleaving_unrank(2, 1, 3), None);
+/// ```
+pub fn interleaving_unrank(left_len: usize, right_len: usize, index: usize) -> Option<Vec<Side>> {
+    if let Some(total) = interleaving_count(left_len, right_len) {
+        if index >= total {
+            return None;
+        }
+    }
+    let (mut left, mut right, mut index) = (left_len, right_len, index);
+    let mut schedule = Vec::with_capacity(left_len.saturating_add(right_len));
+    while left + right > 0 {
+        // Any count too large for `usize` is larger than `index`.
+        let taking_left = match left {
+            0 => Some(0),
+            _ => binomial(left - 1 + right, left - 1),
+        };
+        match taking_left {
+            Some(count) if index >= count => {
+                index -= count;
+                schedule.push(Side::Right);
+                right -= 1;
+            }
+            _ => {
+                schedule.push(Side::Left);
+                left -= 1;
+            }
+        }
+    }
+    Some(schedule)
+}
+
+/// Checks whether `merged` interleaves `a` and `b` while keeping the order within each,
+/// returning the schedule that produces it if so.
+///
+/// When several schedules produce `merged`, the first one in the order of [`interleavings`] is returned.
+/// This takes `O(a.len() * b.len())` time and memory.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{is_interleaving, Side::{Left, Right}};
+///
+/// assert_eq!(is_interleaving(&[1, 2], &[3], &[1, 3, 2]), Some(vec![Left, Right, Left]));
+/// assert_eq!(is_interleaving(&[1, 2], &[3], &[2, 3, 1]), None);
+/// ```
+pub fn is_interleaving<T: PartialEq>(a: &[T], b: &[T], merged: &[T]) -> Option<Vec<Side>> {
+    let (m, n) = (a.len(), b.len());
+    if m + n != merged.len() {
+        return None;
+    }
+    // `possible[i * (n + 1) + j]` tells whether `a[i..]` and `b[j..]` can make up `merged[i + j..]`.
+    let at = |i: usize, j: usize| i * (n + 1) + j;
+    let mut possible = vec![false; (m + 1) * (n + 1)];
+    possible[at(m, n)] = true;
+    for i in (0..=m).rev() {
+        for j in (0..=n).rev() {
+            if i < m && a[i] == merged[i + j] && possible[at(i + 1, j)] {
+                possible[at(i, j)] = true;
+            }
+            if j < n && b[j] == merged[i + j] && possible[at(i, j + 1)] {
+                possible[at(i, j)] = true;
+            }
+        }
+    }
+    if !possible[at(0, 0)] {
+        return None;
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut schedule = Vec::with_capacity(m + n);
+    while i + j < m + n {
+        if i < m && a[i] == merged[i + j] && possible[at(i + 1, j)] {
+            schedule.push(Side::Left);
+            i += 1;
+        } else {
+            schedule.push(Side::Right);
+            j += 1;
+        }
+    }
+    Some(schedule)
+}
+
+/// Checks whether `merged` is exactly what alternating between `a` and `b` in the given [`Mode`] produces.
+///
+/// For [`Mode::Gapped`], `merged` is compared with the items up to the first `None`.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{matches_mode, Mode};
+///
+/// let a = [1, 2];
+/// let b = [3, 4, 5];
+///
+/// assert!(matches_mode(&a, &b, &[1, 3, 2, 4, 5], Mode::All));
+/// assert!(matches_mode(&a, &b, &[1, 3, 2, 4], Mode::NoRemainder));
+/// assert!(!matches_mode(&a, &b, &[1, 3, 2, 4], Mode::All));
+/// ```
+pub fn matches_mode<T: PartialEq>(a: &[T], b: &[T], merged: &[T], mode: Mode) -> bool {
+    let (a, b, merged) = (a.iter(), b.iter(), merged.iter());
+    match mode {
+        Mode::Gapped => a.alternate_with(b).eq(merged),
+        Mode::All => a.alternate_with_all(b).eq(merged),
+        Mode::NoRemainder => a.alternate_with_no_remainder(b).eq(merged),
+    }
+}
+
+/// `n choose k`, or `None` if it overflows `usize`.
+fn binomial(n: usize, k: usize) -> Option<usize> {
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 1..=k as u128 {
+        // Every partial result is itself a binomial coefficient no larger than the final one,
+        // so checking each step is enough, and the product always fits in `u128`.
+        result = result * (n as u128 - k as u128 + i) / i;
+        if result > usize::MAX as u128 {
+            return None;
+        }
+    }
+    Some(result as usize)
+}
+
+fn schedule_at_start(left_len: usize, right_len: usize) -> Vec<bool> {
+    let mut schedule = vec![false; left_len];
+    schedule.resize(left_len + right_len, true);
+    schedule
+}
+
+/// Advance to the next schedule in lexicographic order, returning `false` if it was the last.
+fn next_schedule(schedule: &mut [bool]) -> bool {
+    let Some(pivot) = schedule.windows(2).rposition(|w| !w[0] && w[1]) else {
+        return false;
+    };
+    let successor = schedule
+        .iter()
+        .rposition(|&right| right)
+        .expect("found above");
+    schedule.swap(pivot, successor);
+    schedule[pivot + 1..].reverse();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_interleavings() {
+        let all: Vec<_> = interleavings(&[1, 2], &[3, 4]).collect();
+
+        assert_eq!(
+            all,
+            [
+                [1, 2, 3, 4],
+                [1, 3, 2, 4],
+                [1, 3, 4, 2],
+                [3, 1, 2, 4],
+                [3, 1, 4, 2],
+                [3, 4, 1, 2],
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let empty: [i32; 0] = [];
+
+        assert_eq!(interleavings(&empty, &empty).collect::<Vec<_>>(), [vec![]]);
+        assert_eq!(interleavings(&[1, 2], &empty).collect::<Vec<_>>(), [[1, 2]]);
+    }
+
+    #[test]
+    fn count_matches() {
+        for m in 0..6 {
+            for n in 0..6 {
+                let a: Vec<_> = (0..m).collect();
+                let b: Vec<_> = (m..m + n).collect();
+                let iter = interleavings(&a, &b);
+                let expected = interleaving_count(m, n).unwrap();
+
+                assert_eq!(iter.size_hint(), (expected, Some(expected)));
+                assert_eq!(iter.count(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn count_overflow() {
+        assert_eq!(interleaving_count(usize::MAX, 1), None);
+        assert_eq!(interleaving_count(usize::MAX, 0), Some(1));
+        assert_eq!(interleaving_count(usize::MAX - 1, 1), Some(usize::MAX));
+        assert_eq!(interleaving_count(33, 33), Some(7_219_428_434_016_265_740));
+        assert_eq!(interleaving_count(34, 34), None);
+    }
+
+    #[test]
+    fn rank_unrank_round_trip() {
+        let a = [0, 1, 2];
+        let b = [3, 4, 5, 6];
+
+        for (index, merged) in interleavings(&a, &b).enumerate() {
+            let schedule: Vec<_> = merged
+                .iter()
+                .map(|&n| if n < 3 { Side::Left } else { Side::Right })
+                .collect();
+
+            assert_eq!(interleaving_rank(&schedule), Some(index));
+            assert_eq!(interleaving_unrank(3, 4, index), Some(schedule));
+        }
+        assert_eq!(interleaving_unrank(3, 4, 35), None);
+    }
+
+    #[test]
+    fn unrank_huge() {
+        let last = interleaving_unrank(100, 100, usize::MAX).unwrap();
+
+        assert_eq!(last.len(), 200);
+        assert_eq!(interleaving_rank(&last), Some(usize::MAX));
+    }
+
+    #[test]
+    fn nth_skips() {
+        let a = [0, 1, 2];
+        let b = [3, 4, 5];
+
+        let mut iter = interleavings(&a, &b);
+        let all: Vec<_> = iter.clone().collect();
+
+        assert_eq!(iter.nth(5).as_ref(), Some(&all[5]));
+        assert_eq!(iter.next().as_ref(), Some(&all[6]));
+        assert_eq!(iter.nth(12).as_ref(), Some(&all[19]));
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn nth_past_usize_max() {
+        let a: Vec<u8> = (0..40).collect();
+        let b: Vec<u8> = (40..80).collect();
+        assert_eq!(interleaving_count(a.len(), b.len()), None);
+
+        let mut iter = interleavings(&a, &b);
+        assert!(iter.nth(usize::MAX - 1).is_some());
+        let mut stepped = iter.clone();
+
+        // The position of `iter` is now `usize::MAX`, so skipping goes past it.
+        let skipped = iter.nth(5);
+        assert!(skipped.is_some());
+        assert_eq!(skipped, stepped.by_ref().take(6).last());
+        assert_eq!(iter.nth(2), stepped.nth(2));
+        assert_eq!(iter.next(), stepped.next());
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn verify_all_interleavings() {
+        let a = [0, 1, 2];
+        let b = [3, 4];
+
+        for (index, merged) in interleavings(&a, &b).enumerate() {
+            let schedule = is_interleaving(&a, &b, &merged).unwrap();
+
+            assert_eq!(interleaving_rank(&schedule), Some(index));
+        }
+    }
+
+    #[test]
+    fn verify_ambiguous() {
+        let schedule = is_interleaving(&[1, 1], &[1, 2], &[1, 1, 2, 1]);
+
+        assert_eq!(
+            schedule,
+            Some(vec![Side::Left, Side::Right, Side::Right, Side::Left])
+        );
+        assert_eq!(
+            is_interleaving(&[1, 1], &[1, 2], &[1, 1, 1, 2]).map(|s| s.len()),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn verify_rejects() {
+        assert_eq!(is_interleaving(&[1, 2], &[3], &[1, 2]), None);
+        assert_eq!(is_interleaving(&[1, 2], &[3], &[1, 2, 3, 4]), None);
+        assert_eq!(is_interleaving(&[1, 2], &[3], &[1, 2, 4]), None);
+        assert_eq!(is_interleaving(&[1, 2], &[1], &[2, 1, 1]), None);
+        assert_eq!(is_interleaving::<i32>(&[], &[], &[]), Some(vec![]));
+    }
+
+    #[test]
+    fn modes() {
+        let a = [1, 2];
+        let b = [3, 4, 5];
+
+        assert!(matches_mode(&a, &b, &[1, 3, 2, 4], Mode::Gapped));
+        assert!(matches_mode(&a, &b, &[1, 3, 2, 4, 5], Mode::All));
+        assert!(matches_mode(&a, &b, &[1, 3, 2, 4], Mode::NoRemainder));
+        assert!(matches_mode(&b, &a, &[3, 1, 4, 2, 5], Mode::NoRemainder));
+        assert!(!matches_mode(&a, &b, &[1, 3, 2, 4, 5], Mode::NoRemainder));
+        assert!(!matches_mode(&a, &b, &[1, 3, 2], Mode::All));
+    }
+}
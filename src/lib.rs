@@ -1,18 +1,91 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::invalid_rust_codeblocks)]
 #![deny(rustdoc::missing_crate_level_docs)]
 #![warn(rustdoc::invalid_codeblock_attributes)]
 
+use core::{fmt, iter};
+
 mod alternating;
 mod alternating_all;
 mod alternating_no_remainder;
+mod alternating_random;
+mod array;
+mod bits;
+mod concat;
+#[cfg(feature = "std")]
+mod interleavings;
+mod mode;
+mod planes;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod prefetch;
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "std")]
+mod receiver;
+#[cfg(feature = "std")]
+mod record;
+mod shuffle;
+mod side;
+mod slices;
+#[cfg(feature = "futures-core")]
+mod stream;
+mod try_alternating;
+mod tuple;
 mod utils;
+#[cfg(feature = "std")]
+mod vectored;
+#[cfg(feature = "std")]
+mod write;
 
 pub use alternating::Alternating;
 pub use alternating_all::AlternatingAll;
 pub use alternating_no_remainder::AlternatingNoRemainder;
+pub use alternating_random::AlternatingRandom;
+pub use array::{alternate_array, AlternatingArray};
+pub use bits::{
+    deinterleave_bits, deinterleave_bits3, interleave_bits, interleave_bits3, BitInterleave,
+    BitInterleave3,
+};
+pub use concat::AlternatingDisplay;
+#[cfg(feature = "std")]
+pub use interleavings::{
+    interleaving_count, interleaving_rank, interleaving_unrank, interleavings, is_interleaving,
+    matches_mode, Interleavings,
+};
+pub use mode::Mode;
+pub use planes::{deinterleave_planes, interleave_planes, PlanesError};
+#[cfg(feature = "std")]
+pub use pool::{Handle, RoundRobinPool};
+#[cfg(feature = "std")]
+pub use prefetch::AlternatingPrefetch;
+#[cfg(feature = "std")]
+pub use read::{AlternatingBufRead, AlternatingRead};
+#[cfg(feature = "std")]
+pub use receiver::RoundRobinReceiver;
+#[cfg(feature = "std")]
+pub use record::Record;
+pub use shuffle::{
+    deinterleave_in_place, deinterleave_in_place_at, in_shuffle_in_place, in_unshuffle_in_place,
+    interleave_in_place,
+};
+pub use side::Side;
+pub use slices::{deinterleave_slices, interleave_slices, Primitive};
+#[cfg(feature = "futures-core")]
+pub use stream::{AlternatingStreamExt, MergeFair};
+pub use try_alternating::{ErrorPolicy, TryAlternating, TrySide};
+pub use tuple::{
+    AlternatingMany, AlternatingTuple, Either2, Either3, Either4, Either5, Either6, Either7,
+    Either8,
+};
+#[cfg(feature = "std")]
+pub use vectored::write_all_alternating;
+#[cfg(feature = "std")]
+pub use write::{AlternatingWrite, SinkError};
 
 /// Extension trait that provides methods for creating alternating iterators.
 ///
@@ -145,6 +218,479 @@ pub trait AlternatingExt: Iterator {
     {
         AlternatingNoRemainder::new(self, other)
     }
+
+    /// Takes two iterators and creates a new iterator over both,
+    /// picking the side of each item at random.
+    ///
+    /// Both sides are equally likely by default,
+    /// see [`AlternatingRandom::left_probability`] to change that.
+    /// The randomness comes from a small built-in generator seeded with `seed`,
+    /// so the same seed always gives the same order, on every platform.
+    /// Once one of the iterators is exhausted,
+    /// the remaining items from the other iterator will be returned without interruption.
+    ///
+    /// Note that both iterators must have the same [`Item`](Iterator::Item) type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let a = [1, 2, 3];
+    /// let b = [4, 5, 6];
+    ///
+    /// let first: Vec<_> = a.iter().alternate_random(b.iter(), 42).collect();
+    /// let again: Vec<_> = a.iter().alternate_random(b.iter(), 42).collect();
+    /// assert_eq!(first, again);
+    ///
+    /// let left_first: Vec<_> = a.iter().alternate_random(b.iter(), 42).left_probability(1.0).collect();
+    /// assert_eq!(left_first, [&1, &2, &3, &4, &5, &6]);
+    /// ```
+    fn alternate_random<I>(self, other: I, seed: u64) -> AlternatingRandom<Self, I::IntoIter>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Self::Item>,
+    {
+        AlternatingRandom::new(self, other, seed)
+    }
+
+    /// Takes two iterators of known lengths and creates a new iterator over both,
+    /// picking one of all the ways to interleave them with equal probability.
+    ///
+    /// This is a riffle shuffle: each item comes from a side with a probability
+    /// proportional to the number of items that side has left.
+    /// The randomness comes from a small built-in generator seeded with `seed`,
+    /// so the same seed always gives the same order, on every platform.
+    ///
+    /// Note that both iterators must have the same [`Item`](Iterator::Item) type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let a = [1, 2, 3];
+    /// let b = [4, 5];
+    ///
+    /// let merged: Vec<_> = a.iter().alternate_riffle(b.iter(), 7).collect();
+    ///
+    /// // The relative order within each side is preserved.
+    /// assert_eq!(merged.iter().filter(|&&&n| n <= 3).collect::<Vec<_>>(), [&&1, &&2, &&3]);
+    /// assert_eq!(merged.iter().filter(|&&&n| n > 3).collect::<Vec<_>>(), [&&4, &&5]);
+    /// ```
+    fn alternate_riffle<I>(self, other: I, seed: u64) -> AlternatingRandom<Self, I::IntoIter>
+    where
+        Self: Sized + iter::ExactSizeIterator,
+        I: IntoIterator<Item = Self::Item>,
+        I::IntoIter: iter::ExactSizeIterator,
+    {
+        AlternatingRandom::riffle(self, other, seed)
+    }
+
+    #[cfg(feature = "std")]
+    /// Takes two iterators and creates a new iterator over both in an alternating fashion,
+    /// running each of them on its own thread so that producing their items overlaps.
+    ///
+    /// Each thread sends up to `capacity` items ahead through a bounded channel.
+    /// The items arrive in the same order as from [`alternate_with`](AlternatingExt::alternate_with),
+    /// or the other alternation modes when chosen with [`AlternatingPrefetch::mode`].
+    /// A panic in either iterator is resumed on the consuming thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::{AlternatingExt, Mode};
+    ///
+    /// let a = (1..=2).map(|n| n * 10);
+    /// let b = (1..=3).map(|n| n * 100);
+    ///
+    /// let iter = a.alternate_with_prefetch(b, 16).mode(Mode::All);
+    ///
+    /// assert_eq!(iter.collect::<Vec<_>>(), [10, 100, 20, 200, 300]);
+    /// ```
+    fn alternate_with_prefetch<I>(
+        self,
+        other: I,
+        capacity: usize,
+    ) -> AlternatingPrefetch<Self::Item>
+    where
+        Self: Sized + Send + 'static,
+        Self::Item: Send + 'static,
+        I: IntoIterator<Item = Self::Item>,
+        I::IntoIter: Send + 'static,
+    {
+        AlternatingPrefetch::new(self, other, capacity)
+    }
+
+    /// Takes two iterators, possibly with different [`Item`](Iterator::Item) types,
+    /// maps the items of each with its own closure,
+    /// and creates a new iterator over both in an alternating fashion,
+    /// like [`alternate_with`](AlternatingExt::alternate_with).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Event {
+    ///     Request(u32),
+    ///     Response(String),
+    /// }
+    ///
+    /// let requests = [1, 2];
+    /// let responses = ["ok", "err", "ok"];
+    ///
+    /// let mut iter = requests.into_iter().alternate_map(
+    ///     responses,
+    ///     Event::Request,
+    ///     |s| Event::Response(s.to_string()),
+    /// );
+    ///
+    /// assert_eq!(iter.next(), Some(Event::Request(1)));
+    /// assert_eq!(iter.next(), Some(Event::Response("ok".to_string())));
+    /// assert_eq!(iter.next(), Some(Event::Request(2)));
+    /// assert_eq!(iter.next(), Some(Event::Response("err".to_string())));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.next(), Some(Event::Response("ok".to_string())));
+    /// ```
+    fn alternate_map<I, T, FL, FR>(
+        self,
+        other: I,
+        f_left: FL,
+        f_right: FR,
+    ) -> Alternating<iter::Map<Self, FL>, iter::Map<I::IntoIter, FR>>
+    where
+        Self: Sized,
+        I: IntoIterator,
+        FL: FnMut(Self::Item) -> T,
+        FR: FnMut(I::Item) -> T,
+    {
+        Alternating::new(self.map(f_left), other.into_iter().map(f_right))
+    }
+
+    /// Takes two iterators, possibly with different [`Item`](Iterator::Item) types,
+    /// maps the items of each with its own closure,
+    /// and creates a new iterator over both in an alternating fashion,
+    /// like [`alternate_with_all`](AlternatingExt::alternate_with_all).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let a = [1, 2];
+    /// let b = ["3", "4", "5"];
+    ///
+    /// let iter = a.into_iter().alternate_map_all(b, |n| n * 10, |s| s.parse().unwrap());
+    ///
+    /// assert_eq!(iter.size_hint(), (5, Some(5)));
+    /// assert_eq!(iter.collect::<Vec<i32>>(), [10, 3, 20, 4, 5]);
+    /// ```
+    fn alternate_map_all<I, T, FL, FR>(
+        self,
+        other: I,
+        f_left: FL,
+        f_right: FR,
+    ) -> AlternatingAll<iter::Map<Self, FL>, iter::Map<I::IntoIter, FR>>
+    where
+        Self: Sized,
+        I: IntoIterator,
+        FL: FnMut(Self::Item) -> T,
+        FR: FnMut(I::Item) -> T,
+    {
+        AlternatingAll::new(self.map(f_left), other.into_iter().map(f_right))
+    }
+
+    /// Takes two iterators, possibly with different [`Item`](Iterator::Item) types,
+    /// maps the items of each with its own closure,
+    /// and creates a new iterator over both in an alternating fashion,
+    /// like [`alternate_with_no_remainder`](AlternatingExt::alternate_with_no_remainder).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let a = [1, 2];
+    /// let b = ["3", "4", "5"];
+    ///
+    /// let iter = a.into_iter().alternate_map_no_remainder(b, |n| n * 10, |s| s.parse().unwrap());
+    ///
+    /// assert_eq!(iter.collect::<Vec<i32>>(), [10, 3, 20, 4]);
+    /// ```
+    fn alternate_map_no_remainder<I, T, FL, FR>(
+        self,
+        other: I,
+        f_left: FL,
+        f_right: FR,
+    ) -> AlternatingNoRemainder<iter::Map<Self, FL>, iter::Map<I::IntoIter, FR>>
+    where
+        Self: Sized,
+        I: IntoIterator,
+        FL: FnMut(Self::Item) -> T,
+        FR: FnMut(I::Item) -> T,
+    {
+        AlternatingNoRemainder::new(self.map(f_left), other.into_iter().map(f_right))
+    }
+
+    /// Splits an iterator of known length into halves and creates a new iterator over both
+    /// in an alternating fashion, starting with the first half: a perfect out-shuffle.
+    ///
+    /// When the length is odd, the first half holds the extra item.
+    /// The second half is reached through a clone of the iterator, so nothing is buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let deck = [1, 2, 3, 4, 5, 6];
+    ///
+    /// let shuffled: Vec<_> = deck.iter().faro_out().collect();
+    ///
+    /// assert_eq!(shuffled, [&1, &4, &2, &5, &3, &6]);
+    /// ```
+    fn faro_out(self) -> AlternatingAll<iter::Take<Self>, iter::Skip<Self>>
+    where
+        Self: Sized + Clone + iter::ExactSizeIterator,
+    {
+        let half = self.len() - self.len() / 2;
+        AlternatingAll::new(self.clone().take(half), self.skip(half))
+    }
+
+    /// Splits an iterator of known length into halves and creates a new iterator over both
+    /// in an alternating fashion, starting with the second half: a perfect in-shuffle.
+    ///
+    /// When the length is odd, the second half holds the extra item.
+    /// The second half is reached through a clone of the iterator, so nothing is buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let deck = [1, 2, 3, 4, 5, 6];
+    ///
+    /// let shuffled: Vec<_> = deck.iter().faro_in().collect();
+    ///
+    /// assert_eq!(shuffled, [&4, &1, &5, &2, &6, &3]);
+    /// ```
+    fn faro_in(self) -> AlternatingAll<iter::Skip<Self>, iter::Take<Self>>
+    where
+        Self: Sized + Clone + iter::ExactSizeIterator,
+    {
+        let half = self.len() / 2;
+        AlternatingAll::new(self.clone().skip(half), self.take(half))
+    }
+
+    #[cfg(feature = "std")]
+    /// Takes two iterators of strings and concatenates them in an alternating fashion,
+    /// like [`alternate_with_all`](AlternatingExt::alternate_with_all).
+    ///
+    /// The two iterators can yield different kinds of strings.
+    /// The iterators are cloned to measure the pieces first,
+    /// so that the result is allocated once with its exact length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let fragments = ["Hello, ", "! You are ", "."];
+    /// let values = ["Alice".to_string(), 30.to_string()];
+    ///
+    /// let rendered = fragments.iter().alternate_concat(&values);
+    ///
+    /// assert_eq!(rendered, "Hello, Alice! You are 30.");
+    /// ```
+    fn alternate_concat<I>(self, other: I) -> String
+    where
+        Self: Sized + Clone,
+        Self::Item: AsRef<str>,
+        I: IntoIterator,
+        I::IntoIter: Clone,
+        I::Item: AsRef<str>,
+    {
+        concat::join(self, other.into_iter(), "")
+    }
+
+    #[cfg(feature = "std")]
+    /// Takes two iterators of strings and joins them in an alternating fashion
+    /// with `separator` between every two pieces,
+    /// like [`alternate_concat`](AlternatingExt::alternate_concat).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let keys = ["name", "age"];
+    /// let values = ["Alice", "30"];
+    ///
+    /// assert_eq!(keys.iter().alternate_join(values, " "), "name Alice age 30");
+    /// ```
+    fn alternate_join<I>(self, other: I, separator: &str) -> String
+    where
+        Self: Sized + Clone,
+        Self::Item: AsRef<str>,
+        I: IntoIterator,
+        I::IntoIter: Clone,
+        I::Item: AsRef<str>,
+    {
+        concat::join(self, other.into_iter(), separator)
+    }
+
+    /// Takes two iterators of displayable items and creates a value that formats them
+    /// in an alternating fashion, like [`alternate_with_all`](AlternatingExt::alternate_with_all),
+    /// without allocating.
+    ///
+    /// The two iterators can yield different types.
+    /// They are cloned every time the value is formatted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingExt;
+    ///
+    /// let fragments = ["(", ", ", ")"];
+    /// let values = [1.5, -2.0];
+    ///
+    /// let point = fragments.iter().alternate_display(values.iter());
+    ///
+    /// assert_eq!(point.to_string(), "(1.5, -2)");
+    /// ```
+    fn alternate_display<I>(self, other: I) -> AlternatingDisplay<Self, I::IntoIter>
+    where
+        Self: Sized + Clone,
+        Self::Item: fmt::Display,
+        I: IntoIterator,
+        I::IntoIter: Clone,
+        I::Item: fmt::Display,
+    {
+        AlternatingDisplay::new(self, other)
+    }
+
+    /// Takes two iterators of [`Result`]s and creates a new iterator over both in an alternating fashion,
+    /// like [`alternate_with`](AlternatingExt::alternate_with).
+    ///
+    /// By default, the first error is returned and ends the iteration.
+    /// See [`ErrorPolicy`] for how to instead set aside the errors of one side
+    /// and treat it as exhausted, or skip over them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::{AlternatingExt, ErrorPolicy};
+    ///
+    /// let a = [Ok(1), Ok(2)];
+    /// let b = [Ok(3), Err("bad"), Ok(4)];
+    ///
+    /// let mut iter = a.into_iter()
+    ///     .try_alternate_with(b)
+    ///     .right_policy(ErrorPolicy::StopSide);
+    ///
+    /// assert_eq!(iter.next(), Some(Ok(1))); // `a` first
+    /// assert_eq!(iter.next(), Some(Ok(3))); // `b`
+    /// assert_eq!(iter.next(), Some(Ok(2))); // `a`
+    /// assert_eq!(iter.next(), None);        // `b` failed
+    /// assert_eq!(iter.right_error(), Some(&"bad"));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn try_alternate_with<I, T, E>(
+        self,
+        other: I,
+    ) -> TryAlternating<Alternating<TrySide<Self, E>, TrySide<I::IntoIter, E>>>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        I: IntoIterator<Item = Self::Item>,
+    {
+        let (i, j) = try_alternating::sides(self, other.into_iter());
+        TryAlternating::new(Alternating::new(i, j))
+    }
+
+    /// Takes two iterators of [`Result`]s and creates a new iterator over both in an alternating fashion,
+    /// like [`alternate_with_all`](AlternatingExt::alternate_with_all).
+    ///
+    /// By default, the first error is returned and ends the iteration.
+    /// See [`ErrorPolicy`] for how to instead set aside the errors of one side
+    /// and keep pulling from the other, or from both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::{AlternatingExt, ErrorPolicy};
+    ///
+    /// let a = [Ok(1), Err("bad"), Ok(2)];
+    /// let b = [Ok(3), Ok(4), Ok(5)];
+    ///
+    /// let mut iter = a.into_iter().try_alternate_with_all(b);
+    ///
+    /// assert_eq!(iter.next(), Some(Ok(1)));     // `a` first
+    /// assert_eq!(iter.next(), Some(Ok(3)));     // `b`
+    /// assert_eq!(iter.next(), Some(Err("bad")));
+    /// assert_eq!(iter.next(), None);            // stopped at the first error
+    ///
+    /// let mut iter = a.into_iter()
+    ///     .try_alternate_with_all(b)
+    ///     .left_policy(ErrorPolicy::StopSide);
+    ///
+    /// assert_eq!(iter.next(), Some(Ok(1))); // `a` first
+    /// assert_eq!(iter.next(), Some(Ok(3))); // `b`
+    /// assert_eq!(iter.next(), Some(Ok(4))); // `a` failed, so `b`
+    /// assert_eq!(iter.next(), Some(Ok(5))); // also `b`
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.left_error(), Some(&"bad"));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn try_alternate_with_all<I, T, E>(
+        self,
+        other: I,
+    ) -> TryAlternating<AlternatingAll<TrySide<Self, E>, TrySide<I::IntoIter, E>>>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        I: IntoIterator<Item = Self::Item>,
+    {
+        let (i, j) = try_alternating::sides(self, other.into_iter());
+        TryAlternating::new(AlternatingAll::new(i, j))
+    }
+
+    /// Takes two iterators of [`Result`]s and creates a new iterator over both in an alternating fashion,
+    /// like [`alternate_with_no_remainder`](AlternatingExt::alternate_with_no_remainder).
+    ///
+    /// By default, the first error is returned and ends the iteration.
+    /// See [`ErrorPolicy`] for how to instead set aside the errors of one side,
+    /// which then stops the iteration as if that side was exhausted, or skip over them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::{AlternatingExt, ErrorPolicy};
+    ///
+    /// let a = [Ok(1), Err("bad"), Ok(2)];
+    /// let b = [Ok(3), Ok(4), Ok(5)];
+    ///
+    /// let mut iter = a.into_iter()
+    ///     .try_alternate_with_no_remainder(b)
+    ///     .left_policy(ErrorPolicy::StopSide);
+    ///
+    /// assert_eq!(iter.next(), Some(Ok(1))); // `a` first
+    /// assert_eq!(iter.next(), Some(Ok(3))); // `b`
+    /// assert_eq!(iter.next(), None);        // `a` failed
+    /// assert_eq!(iter.left_error(), Some(&"bad"));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn try_alternate_with_no_remainder<I, T, E>(
+        self,
+        other: I,
+    ) -> TryAlternating<AlternatingNoRemainder<TrySide<Self, E>, TrySide<I::IntoIter, E>>>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        I: IntoIterator<Item = Self::Item>,
+    {
+        let (i, j) = try_alternating::sides(self, other.into_iter());
+        TryAlternating::new(AlternatingNoRemainder::new(i, j))
+    }
 }
 
 impl<I> AlternatingExt for I where I: Iterator {}
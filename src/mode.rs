@@ -0,0 +1,13 @@
+#[allow(unused_imports)]
+use crate::{Alternating, AlternatingAll, AlternatingNoRemainder};
+
+/// How an alternation handles one of its iterators running out, one for each of the adaptors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Keep alternating, returning `None` in place of the exhausted iterator, like [`Alternating`].
+    Gapped,
+    /// Return the remaining items of the other iterator, like [`AlternatingAll`].
+    All,
+    /// Stop as soon as the exhausted iterator is reached, like [`AlternatingNoRemainder`].
+    NoRemainder,
+}
@@ -0,0 +1,264 @@
+use core::fmt;
+
+/// Error returned by [`interleave_planes`] and [`deinterleave_planes`] when the lengths do not line up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlanesError {
+    /// A plane is not as long as the first one.
+    PlaneLength {
+        /// Index of the offending plane.
+        plane: usize,
+        /// Its length.
+        len: usize,
+        /// The length of the first plane.
+        expected: usize,
+    },
+    /// The packed buffer is not as long as all the planes together.
+    PackedLength {
+        /// Its length.
+        len: usize,
+        /// The combined length of the planes.
+        expected: usize,
+    },
+}
+
+impl fmt::Display for PlanesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PlaneLength {
+                plane,
+                len,
+                expected,
+            } => write!(
+                f,
+                "plane {} has length {} instead of {}",
+                plane, len, expected
+            ),
+            Self::PackedLength { len, expected } => write!(
+                f,
+                "packed buffer has length {} instead of {}",
+                len, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PlanesError {}
+
+/// Writes the planes, each holding one channel, into `packed` one frame at a time:
+/// the first item of every plane, then the second item of every plane, and so on.
+///
+/// With two planes, this is the same order as
+/// [`alternate_with_all`](crate::AlternatingExt::alternate_with_all).
+/// Two, three and four planes use specialized code.
+///
+/// # Errors
+///
+/// Fails without writing anything if the planes differ in length,
+/// or if `packed` is not exactly as long as all of them together.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::interleave_planes;
+///
+/// let red = [1, 2];
+/// let green = [3, 4];
+/// let blue = [5, 6];
+/// let mut packed = [0; 6];
+///
+/// interleave_planes(&[&red, &green, &blue], &mut packed).unwrap();
+///
+/// assert_eq!(packed, [1, 3, 5, 2, 4, 6]);
+/// ```
+pub fn interleave_planes<T: Copy>(planes: &[&[T]], packed: &mut [T]) -> Result<(), PlanesError> {
+    check(planes.iter().map(|plane| plane.len()), packed.len())?;
+    match *planes {
+        [] => {}
+        [a, b] => interleave_fixed([a, b], packed),
+        [a, b, c] => interleave_fixed([a, b, c], packed),
+        [a, b, c, d] => interleave_fixed([a, b, c, d], packed),
+        _ => {
+            for (c, plane) in planes.iter().enumerate() {
+                for (slot, &item) in packed.iter_mut().skip(c).step_by(planes.len()).zip(*plane) {
+                    *slot = item;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits `packed`, made of one frame after another, into the planes, each receiving one channel.
+///
+/// Inverse of [`interleave_planes`].
+/// Two, three and four planes use specialized code.
+///
+/// # Errors
+///
+/// Fails without writing anything if the planes differ in length,
+/// or if `packed` is not exactly as long as all of them together.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::deinterleave_planes;
+///
+/// let packed = [1, 3, 5, 2, 4, 6];
+/// let (mut red, mut green, mut blue) = ([0; 2], [0; 2], [0; 2]);
+///
+/// deinterleave_planes(&packed, &mut [&mut red, &mut green, &mut blue]).unwrap();
+///
+/// assert_eq!((red, green, blue), ([1, 2], [3, 4], [5, 6]));
+/// ```
+pub fn deinterleave_planes<T: Copy>(
+    packed: &[T],
+    planes: &mut [&mut [T]],
+) -> Result<(), PlanesError> {
+    check(planes.iter().map(|plane| plane.len()), packed.len())?;
+    match planes {
+        [] => {}
+        [a, b] => deinterleave_fixed(packed, [a, b]),
+        [a, b, c] => deinterleave_fixed(packed, [a, b, c]),
+        [a, b, c, d] => deinterleave_fixed(packed, [a, b, c, d]),
+        _ => {
+            let channels = planes.len();
+            for (c, plane) in planes.iter_mut().enumerate() {
+                for (slot, &item) in plane
+                    .iter_mut()
+                    .zip(packed.iter().skip(c).step_by(channels))
+                {
+                    *slot = item;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check(mut lens: impl ExactSizeIterator<Item = usize>, packed: usize) -> Result<(), PlanesError> {
+    let channels = lens.len();
+    let Some(expected) = lens.next() else {
+        return match packed {
+            0 => Ok(()),
+            len => Err(PlanesError::PackedLength { len, expected: 0 }),
+        };
+    };
+    if let Some((plane, len)) = lens.enumerate().find(|&(_, len)| len != expected) {
+        return Err(PlanesError::PlaneLength {
+            plane: plane + 1,
+            len,
+            expected,
+        });
+    }
+    match expected.checked_mul(channels) {
+        Some(total) if total == packed => Ok(()),
+        total => Err(PlanesError::PackedLength {
+            len: packed,
+            expected: total.unwrap_or(usize::MAX),
+        }),
+    }
+}
+
+fn interleave_fixed<T: Copy, const N: usize>(planes: [&[T]; N], packed: &mut [T]) {
+    for (k, frame) in packed.chunks_exact_mut(N).enumerate() {
+        for c in 0..N {
+            frame[c] = planes[c][k];
+        }
+    }
+}
+
+fn deinterleave_fixed<T: Copy, const N: usize>(packed: &[T], planes: [&mut &mut [T]; N]) {
+    for (k, frame) in packed.chunks_exact(N).enumerate() {
+        for c in 0..N {
+            planes[c][k] = frame[c];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for channels in 0..7 {
+            for len in [0, 1, 5, 16] {
+                let planes: Vec<Vec<u32>> = (0..channels)
+                    .map(|c| (0..len).map(|k| (c * 100 + k) as u32).collect())
+                    .collect();
+                let refs: Vec<&[u32]> = planes.iter().map(Vec::as_slice).collect();
+
+                let mut packed = vec![0; channels * len];
+                interleave_planes(&refs, &mut packed).unwrap();
+                for (k, frame) in packed.chunks(channels.max(1)).enumerate() {
+                    for (c, &item) in frame.iter().enumerate() {
+                        assert_eq!(item, planes[c][k]);
+                    }
+                }
+
+                let mut unpacked = vec![vec![0; len]; channels];
+                let mut muts: Vec<&mut [u32]> =
+                    unpacked.iter_mut().map(Vec::as_mut_slice).collect();
+                deinterleave_planes(&packed, &mut muts).unwrap();
+                assert_eq!(unpacked, planes);
+            }
+        }
+    }
+
+    #[test]
+    fn two_planes_match_all() {
+        use crate::AlternatingExt;
+
+        let a = [1, 2, 3];
+        let b = [4, 5, 6];
+        let mut packed = [0; 6];
+
+        interleave_planes(&[&a, &b], &mut packed).unwrap();
+
+        assert!(a.iter().alternate_with_all(&b).eq(&packed));
+    }
+
+    #[test]
+    fn mismatched_planes() {
+        let mut packed = [0; 6];
+
+        assert_eq!(
+            interleave_planes(&[&[1, 2], &[3, 4], &[5]], &mut packed),
+            Err(PlanesError::PlaneLength {
+                plane: 2,
+                len: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(packed, [0; 6]);
+    }
+
+    #[test]
+    fn mismatched_packed() {
+        let (mut a, mut b) = ([0; 2], [0; 2]);
+
+        assert_eq!(
+            deinterleave_planes(&[1, 2, 3], &mut [&mut a, &mut b]),
+            Err(PlanesError::PackedLength {
+                len: 3,
+                expected: 4
+            })
+        );
+        assert_eq!(
+            interleave_planes::<i32>(&[], &mut [1]),
+            Err(PlanesError::PackedLength {
+                len: 1,
+                expected: 0
+            })
+        );
+        assert_eq!(
+            PlanesError::PackedLength {
+                len: 3,
+                expected: 4
+            }
+            .to_string(),
+            "packed buffer has length 3 instead of 4"
+        );
+    }
+}
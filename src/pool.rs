@@ -0,0 +1,259 @@
+/// Takes turns between a changing set of iterators,
+/// the way [`AlternatingAll`](crate::AlternatingAll) takes turns between two.
+///
+/// Each member gets one turn per round, in the order they were added,
+/// and is dropped from the pool once it returns `None`.
+/// Members can be added with [`push`](Self::push) and taken out with [`remove`](Self::remove)
+/// between calls to [`next`](Iterator::next), without disturbing the turns of the others:
+/// a new member joins the back of the line, after every member waiting for its turn.
+///
+/// Iterating returns `None` when the pool is empty, but continues once members are pushed again.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::RoundRobinPool;
+///
+/// let mut pool = RoundRobinPool::new();
+/// let a = pool.push(vec!["a1", "a2", "a3"].into_iter());
+/// pool.push(vec!["b1"].into_iter());
+///
+/// assert_eq!(pool.next(), Some("a1"));
+/// pool.push(vec!["c1", "c2"].into_iter());
+/// assert_eq!(pool.next(), Some("b1"));
+/// assert_eq!(pool.next(), Some("a2"));
+/// assert_eq!(pool.next(), Some("c1"));
+///
+/// // `b` runs out on its next turn and is dropped.
+/// assert_eq!(pool.next(), Some("a3"));
+/// assert_eq!(pool.len(), 2);
+///
+/// assert!(pool.remove(a).is_some());
+/// assert_eq!(pool.collect::<Vec<_>>(), ["c2"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RoundRobinPool<I> {
+    /// Members in turn order, with the handles they were added under.
+    members: Vec<(Handle, I)>,
+    /// Index of the member whose turn it is.
+    turn: usize,
+    next_handle: u64,
+}
+
+/// Identifies a member of a [`RoundRobinPool`], returned by [`RoundRobinPool::push`].
+///
+/// Handles are never reused by the pool that created them,
+/// so a handle of a member that has been removed or dropped stays invalid.
+///
+/// A handle only identifies a member of the pool that returned it.
+/// Handles are numbered per pool, so passing one to another pool
+/// may refer to an unrelated member of that pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle(u64);
+
+impl<I> RoundRobinPool<I>
+where
+    I: Iterator,
+{
+    /// Create an empty `RoundRobinPool`.
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+            turn: 0,
+            next_handle: 0,
+        }
+    }
+
+    /// Add `iter` to the pool, taking its first turn after every current member has had one more.
+    pub fn push(&mut self, iter: impl IntoIterator<IntoIter = I>) -> Handle {
+        let handle = Handle(self.next_handle);
+        self.next_handle += 1;
+        // Placing it just before the member whose turn it is puts it at the back of the line.
+        self.members.insert(self.turn, (handle, iter.into_iter()));
+        if self.members.len() > 1 {
+            self.turn += 1;
+        }
+        handle
+    }
+
+    /// Take the member with the given handle out of the pool, returning it,
+    /// or `None` if it was already removed or dropped after running out.
+    ///
+    /// The other members keep their turns.
+    pub fn remove(&mut self, handle: Handle) -> Option<I> {
+        let index = self.members.iter().position(|(h, _)| *h == handle)?;
+        Some(self.remove_at(index))
+    }
+
+    /// Whether the member with the given handle is still in the pool.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.members.iter().any(|(h, _)| *h == handle)
+    }
+
+    /// Number of members in the pool.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the pool has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    fn remove_at(&mut self, index: usize) -> I {
+        let (_, iter) = self.members.remove(index);
+        if index < self.turn {
+            self.turn -= 1;
+        }
+        if self.turn >= self.members.len() {
+            self.turn = 0;
+        }
+        iter
+    }
+}
+
+impl<I> Default for RoundRobinPool<I>
+where
+    I: Iterator,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> Iterator for RoundRobinPool<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.members.is_empty() {
+            let index = self.turn;
+            match self.members[index].1.next() {
+                Some(item) => {
+                    self.turn = if index + 1 == self.members.len() {
+                        0
+                    } else {
+                        index + 1
+                    };
+                    return Some(item);
+                }
+                None => {
+                    self.remove_at(index);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut lower = 0usize;
+        let mut upper = Some(0usize);
+        for (_, iter) in &self.members {
+            let (iter_lower, iter_upper) = iter.size_hint();
+            lower = lower.saturating_add(iter_lower);
+            upper = upper
+                .zip(iter_upper)
+                .and_then(|(upper, iter_upper)| upper.checked_add(iter_upper));
+        }
+        (lower, upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlternatingExt, Mode};
+
+    type Range = core::ops::Range<u32>;
+
+    #[test]
+    fn matches_all() {
+        let mut pool = RoundRobinPool::new();
+        pool.push(0..3);
+        pool.push(10..15);
+
+        assert_eq!(pool.size_hint(), (8, Some(8)));
+        assert!(pool.eq((0..3).alternate_with_all(10..15)));
+    }
+
+    #[test]
+    fn fair_after_push() {
+        let mut pool: RoundRobinPool<Range> = RoundRobinPool::new();
+        pool.push(0..10);
+        pool.push(10..20);
+        pool.push(20..30);
+
+        assert_eq!(pool.next(), Some(0));
+        assert_eq!(pool.next(), Some(10));
+        pool.push(30..40);
+        // The newcomer waits for `c`, then `a` and `b` again.
+        let round: Vec<_> = pool.by_ref().take(8).collect();
+        assert_eq!(round, [20, 1, 11, 30, 21, 2, 12, 31]);
+
+        let mut pool: RoundRobinPool<Range> = RoundRobinPool::default();
+        pool.push(0..2);
+        assert_eq!(pool.next(), Some(0));
+        pool.push(10..12);
+        assert_eq!(pool.collect::<Vec<_>>(), [1, 10, 11]);
+    }
+
+    #[test]
+    fn fair_after_remove() {
+        let mut pool: RoundRobinPool<Range> = RoundRobinPool::new();
+        let a = pool.push(0..10);
+        let b = pool.push(10..20);
+        let c = pool.push(20..30);
+        let d = pool.push(30..40);
+
+        assert_eq!(pool.by_ref().take(2).collect::<Vec<_>>(), [0, 10]);
+        // Removing a member that already had its turn keeps the next one next.
+        assert_eq!(pool.remove(b), Some(11..20));
+        assert_eq!(pool.next(), Some(20));
+        // Removing the member whose turn it is passes the turn on.
+        assert_eq!(pool.remove(d), Some(30..40));
+        assert_eq!(pool.next(), Some(1));
+        assert_eq!(pool.remove(c), Some(21..30));
+        assert_eq!(pool.by_ref().take(2).collect::<Vec<_>>(), [2, 3]);
+
+        assert_eq!(pool.remove(b), None);
+        assert!(pool.contains(a));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn drops_exhausted() {
+        let mut pool = RoundRobinPool::new();
+        let a = pool.push(0..1);
+        let b = pool.push(10..13);
+
+        assert_eq!(pool.by_ref().take(3).collect::<Vec<_>>(), [0, 10, 11]);
+        assert_eq!(pool.len(), 1);
+        assert!(!pool.contains(a));
+        assert_eq!(pool.remove(a), None);
+
+        assert_eq!(pool.next(), Some(12));
+        assert_eq!(pool.next(), None);
+        assert!(pool.is_empty());
+        assert!(!pool.contains(b));
+
+        // Handles are not reused, and the pool resumes once refilled.
+        let c = pool.push(20..21);
+        assert_ne!(c, a);
+        assert_ne!(c, b);
+        assert_eq!(pool.next(), Some(20));
+        assert_eq!(pool.next(), None);
+    }
+
+    #[test]
+    fn matches_array_all() {
+        let mut pool = RoundRobinPool::new();
+        for range in [0..3, 10..11, 20..24, 30..32] {
+            pool.push(range);
+        }
+        let array = crate::alternate_array([0..3, 10..11, 20..24, 30..32]).mode(Mode::All);
+
+        assert!(pool.eq(array));
+    }
+}
@@ -0,0 +1,276 @@
+use std::panic;
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+
+#[allow(unused_imports)]
+use crate::AlternatingExt;
+use crate::{Alternating, AlternatingAll, AlternatingNoRemainder, Mode};
+
+/// Struct for alternating between the items of two iterators that each run on their own thread.
+///
+/// Items arrive in the same order as from [`Alternating`], [`AlternatingAll`] or
+/// [`AlternatingNoRemainder`], depending on the [`Mode`].
+/// Each iterator is treated as fused: after returning `None` once, its thread is finished.
+///
+/// If an iterator panics, the panic is resumed on the consuming thread
+/// when the iterator's turn comes after its last item.
+///
+/// Dropping this struct does not wait for the threads.
+/// They stop once they try to send their next item.
+///
+/// This struct is created by the [`AlternatingExt::alternate_with_prefetch`] method, see its documentation for more.
+#[derive(Debug)]
+pub struct AlternatingPrefetch<T> {
+    inner: Inner<T>,
+}
+
+#[derive(Debug)]
+enum Inner<T> {
+    Gapped(Alternating<Worker<T>, Worker<T>>),
+    All(AlternatingAll<Worker<T>, Worker<T>>),
+    NoRemainder(AlternatingNoRemainder<Worker<T>, Worker<T>>),
+}
+
+impl<T: Send + 'static> AlternatingPrefetch<T> {
+    /// Create a new `AlternatingPrefetch` iterator from two other iterables,
+    /// each buffering up to `capacity` items ahead of the consumer.
+    ///
+    /// Alternative to [`AlternatingExt::alternate_with_prefetch`]. There is no difference.
+    pub fn new<I, J>(i: I, j: J, capacity: usize) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: Send + 'static,
+        J: IntoIterator<Item = T>,
+        J::IntoIter: Send + 'static,
+    {
+        Self {
+            inner: Inner::Gapped(Alternating::new(
+                Worker::spawn(i.into_iter(), capacity),
+                Worker::spawn(j.into_iter(), capacity),
+            )),
+        }
+    }
+}
+
+impl<T> AlternatingPrefetch<T> {
+    /// Switch to the order of the given [`Mode`], which is [`Mode::Gapped`] by default.
+    ///
+    /// Items already returned are kept, and the next item follows the rules of the new mode.
+    pub fn mode(self, mode: Mode) -> Self {
+        let inner = match (self.inner, mode) {
+            (Inner::Gapped(inner), Mode::All) => Inner::All(inner.into_all()),
+            (Inner::Gapped(inner), Mode::NoRemainder) => {
+                Inner::NoRemainder(inner.into_no_remainder())
+            }
+            (Inner::All(inner), Mode::Gapped) => Inner::Gapped(inner.into_gapped()),
+            (Inner::All(inner), Mode::NoRemainder) => Inner::NoRemainder(inner.into_no_remainder()),
+            (Inner::NoRemainder(inner), Mode::Gapped) => Inner::Gapped(inner.into_gapped()),
+            (Inner::NoRemainder(inner), Mode::All) => Inner::All(inner.into_all()),
+            (inner, _) => inner,
+        };
+        Self { inner }
+    }
+}
+
+impl<T> Iterator for AlternatingPrefetch<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            Inner::Gapped(inner) => inner.next(),
+            Inner::All(inner) => inner.next(),
+            Inner::NoRemainder(inner) => inner.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            Inner::Gapped(inner) => inner.size_hint(),
+            Inner::All(inner) => inner.size_hint(),
+            Inner::NoRemainder(inner) => inner.size_hint(),
+        }
+    }
+}
+
+/// The receiving end of an iterator running on its own thread.
+#[derive(Debug)]
+struct Worker<T> {
+    receiver: Receiver<T>,
+    /// `None` once the thread has been joined.
+    handle: Option<JoinHandle<()>>,
+    /// The size hint of the iterator when spawned, less the items received since.
+    size_hint: (usize, Option<usize>),
+}
+
+impl<T: Send + 'static> Worker<T> {
+    fn spawn<I>(iter: I, capacity: usize) -> Self
+    where
+        I: Iterator<Item = T> + Send + 'static,
+    {
+        let size_hint = iter.size_hint();
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let handle = thread::spawn(move || {
+            for item in iter {
+                if sender.send(item).is_err() {
+                    // The consumer is gone.
+                    break;
+                }
+            }
+        });
+        Self {
+            receiver,
+            handle: Some(handle),
+            size_hint,
+        }
+    }
+}
+
+impl<T> Iterator for Worker<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.receiver.recv() {
+            Ok(item) => {
+                let (lower, upper) = self.size_hint;
+                self.size_hint = (
+                    lower.saturating_sub(1),
+                    upper.map(|upper| upper.saturating_sub(1)),
+                );
+                Some(item)
+            }
+            Err(mpsc::RecvError) => {
+                self.size_hint = (0, Some(0));
+                // The sender is dropped when the thread finishes, normally or by panicking.
+                if let Some(Err(payload)) = self.handle.take().map(JoinHandle::join) {
+                    panic::resume_unwind(payload);
+                }
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.size_hint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_order() {
+        for (a, b) in [
+            (0..5, 10..12),
+            (0..2, 10..15),
+            (0..3, 10..13),
+            (0..0, 10..11),
+        ] {
+            let gapped: Vec<_> = a.clone().alternate_with_prefetch(b.clone(), 1).collect();
+            let expected: Vec<_> = a.clone().alternate_with(b.clone()).collect();
+            assert_eq!(gapped, expected);
+
+            let all: Vec<_> = a
+                .clone()
+                .alternate_with_prefetch(b.clone(), 0)
+                .mode(Mode::All)
+                .collect();
+            assert_eq!(
+                all,
+                a.clone().alternate_with_all(b.clone()).collect::<Vec<_>>()
+            );
+
+            let no_remainder: Vec<_> = a
+                .clone()
+                .alternate_with_prefetch(b.clone(), 4)
+                .mode(Mode::NoRemainder)
+                .collect();
+            assert_eq!(
+                no_remainder,
+                a.alternate_with_no_remainder(b).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn gapped_continues() {
+        let mut iter = [1].into_iter().alternate_with_prefetch([2, 3, 4], 2);
+
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn runs_concurrently() {
+        use std::sync::{Arc, Barrier};
+
+        // Each side waits for the other to start, which deadlocks unless both run at once.
+        let barrier = Arc::new(Barrier::new(2));
+        let left = {
+            let barrier = Arc::clone(&barrier);
+            (0..3).inspect(move |&n| {
+                if n == 0 {
+                    barrier.wait();
+                }
+            })
+        };
+        let right = (10..13).inspect(move |&n| {
+            if n == 10 {
+                barrier.wait();
+            }
+        });
+
+        let merged: Vec<_> = left.alternate_with_prefetch(right, 0).collect();
+
+        assert_eq!(merged, [0, 10, 1, 11, 2, 12]);
+    }
+
+    #[test]
+    fn size_hints() {
+        let mut iter = (0..5).alternate_with_prefetch(10..12, 2).mode(Mode::All);
+        let mut expected = (0..5).alternate_with_all(10..12);
+        loop {
+            assert_eq!(iter.size_hint(), expected.size_hint());
+            if iter.next().is_none() {
+                break;
+            }
+            expected.next();
+        }
+
+        let even = || (0..).filter(|n| n % 2 == 0);
+        let iter = (0..).alternate_with_prefetch(even(), 1);
+        let expected = (0..).alternate_with_no_remainder(even());
+        assert_eq!(
+            iter.mode(Mode::NoRemainder).size_hint(),
+            expected.size_hint()
+        );
+    }
+
+    #[test]
+    fn early_drop() {
+        let mut iter = (0..).alternate_with_prefetch(0.., 8);
+
+        assert_eq!(iter.next(), Some(0));
+        drop(iter);
+    }
+
+    #[test]
+    #[should_panic(expected = "worker failed")]
+    fn propagates_panic() {
+        let failing = (0..3).inspect(|&n| {
+            if n == 2 {
+                panic!("worker failed");
+            }
+        });
+        let mut iter = failing.alternate_with_prefetch(10..20, 1).mode(Mode::All);
+
+        assert_eq!(iter.by_ref().take(4).collect::<Vec<_>>(), [0, 10, 1, 11]);
+        iter.next();
+    }
+}
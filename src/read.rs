@@ -0,0 +1,511 @@
+use std::io::{self, BufRead, BufReader, Read};
+
+use crate::{Mode, Record, Side};
+
+/// Reader for alternating between the records of two readers.
+///
+/// Whole [`Record`]s are taken in turn, starting with the left reader.
+/// When a reader runs out, the [`Mode`] decides what happens next:
+///
+/// - [`Mode::Gapped`]: the turn of the exhausted reader reads as the end of the stream,
+///   and reading again continues with the other one, the way [`Alternating`](crate::Alternating) returns `None`.
+/// - [`Mode::All`]: the remaining records of the other reader follow.
+/// - [`Mode::NoRemainder`]: the stream ends.
+///
+/// Records are passed through without copying, except for the length of a [`Record::Frame`].
+///
+/// # Errors
+///
+/// Besides the errors of the readers, a frame cut short by the end of its reader
+/// fails with [`io::ErrorKind::UnexpectedEof`].
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{AlternatingBufRead, Mode};
+/// use std::io::{BufRead, Cursor};
+///
+/// let a = Cursor::new("a1\na2\n");
+/// let b = Cursor::new("b1\nb2\nb3\n");
+///
+/// let reader = AlternatingBufRead::new(a, b).mode(Mode::All);
+/// let lines: Vec<_> = reader.lines().map(Result::unwrap).collect();
+///
+/// assert_eq!(lines, ["a1", "b1", "a2", "b2", "b3"]);
+/// ```
+#[derive(Debug)]
+pub struct AlternatingBufRead<R, S> {
+    left: R,
+    right: S,
+    record: Record,
+    mode: Mode,
+    turn: Side,
+    /// Marks that the other reader has been exhausted in [`Mode::All`].
+    only: Option<Side>,
+    /// Marks the end of the stream in [`Mode::NoRemainder`].
+    done: bool,
+    progress: Progress,
+}
+
+/// How far into its current record the reader whose turn it is has been read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Progress {
+    /// No part of the record has been read.
+    Start,
+    /// Within a line. `end` marks that the last buffer returned reaches its newline.
+    Line { served: usize, end: bool },
+    /// Serving the length of a frame, of which `pos` bytes have been consumed.
+    Header {
+        header: [u8; 4],
+        pos: usize,
+        body: usize,
+    },
+    /// A chunk or the body of a frame, with this many bytes left.
+    Remaining { left: usize, frame: bool },
+}
+
+impl<R, S> AlternatingBufRead<R, S>
+where
+    R: BufRead,
+    S: BufRead,
+{
+    /// Create a new `AlternatingBufRead` taking turns line by line in [`Mode::Gapped`].
+    pub fn new(left: R, right: S) -> Self {
+        Self {
+            left,
+            right,
+            record: Record::Line,
+            mode: Mode::Gapped,
+            turn: Side::Left,
+            only: None,
+            done: false,
+            progress: Progress::Start,
+        }
+    }
+}
+
+impl<R, S> AlternatingBufRead<R, S> {
+    /// Take turns with the given kind of [`Record`], which is [`Record::Line`] by default.
+    ///
+    /// Should be set before reading, since the current record is finished the old way.
+    pub fn record(mut self, record: Record) -> Self {
+        self.record = record;
+        self
+    }
+
+    /// Handle exhaustion according to the given [`Mode`], which is [`Mode::Gapped`] by default.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Gets references to the underlying readers.
+    pub fn get_ref(&self) -> (&R, &S) {
+        (&self.left, &self.right)
+    }
+
+    /// Unwraps this `AlternatingBufRead`, returning the underlying readers.
+    ///
+    /// Any part of a frame's length that was read but not consumed is lost.
+    pub fn into_inner(self) -> (R, S) {
+        (self.left, self.right)
+    }
+
+    fn finish_record(&mut self) {
+        self.progress = Progress::Start;
+        if self.only.is_none() {
+            self.turn = self.turn.other();
+        }
+    }
+}
+
+impl<R, S> AlternatingBufRead<R, S>
+where
+    R: BufRead,
+    S: BufRead,
+{
+    fn side_fill_buf(&mut self, side: Side) -> io::Result<&[u8]> {
+        match side {
+            Side::Left => self.left.fill_buf(),
+            Side::Right => self.right.fill_buf(),
+        }
+    }
+
+    fn side_consume(&mut self, side: Side, amt: usize) {
+        match side {
+            Side::Left => self.left.consume(amt),
+            Side::Right => self.right.consume(amt),
+        }
+    }
+
+    /// Begin the next record of the reader whose turn it is, returning `false` at the end of the stream.
+    fn start_record(&mut self) -> io::Result<bool> {
+        loop {
+            if self.done {
+                return Ok(false);
+            }
+            if !self.side_fill_buf(self.turn)?.is_empty() {
+                break;
+            }
+            match (self.mode, self.only) {
+                (Mode::Gapped, _) => {
+                    self.turn = self.turn.other();
+                    return Ok(false);
+                }
+                (Mode::All, None) => {
+                    self.turn = self.turn.other();
+                    self.only = Some(self.turn);
+                }
+                (Mode::All, Some(_)) | (Mode::NoRemainder, _) => {
+                    self.done = true;
+                }
+            }
+        }
+        self.progress = match self.record {
+            Record::Line => Progress::Line {
+                served: 0,
+                end: false,
+            },
+            Record::Chunk(size) => Progress::Remaining {
+                left: size.max(1),
+                frame: false,
+            },
+            Record::Frame => {
+                let mut header = [0; 4];
+                match self.turn {
+                    Side::Left => self.left.read_exact(&mut header),
+                    Side::Right => self.right.read_exact(&mut header),
+                }
+                .map_err(|error| match error.kind() {
+                    io::ErrorKind::UnexpectedEof => truncated(),
+                    _ => error,
+                })?;
+                let body = usize::try_from(u32::from_be_bytes(header)).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "frame length too large")
+                })?;
+                Progress::Header {
+                    header,
+                    pos: 0,
+                    body,
+                }
+            }
+        };
+        Ok(true)
+    }
+}
+
+impl<R, S> BufRead for AlternatingBufRead<R, S>
+where
+    R: BufRead,
+    S: BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let served = loop {
+            match self.progress {
+                Progress::Start => {
+                    if !self.start_record()? {
+                        return Ok(&[]);
+                    }
+                }
+                Progress::Line { .. } => {
+                    let buf = self.side_fill_buf(self.turn)?;
+                    match buf.iter().position(|&byte| byte == b'\n') {
+                        Some(newline) => {
+                            self.progress = Progress::Line {
+                                served: newline + 1,
+                                end: true,
+                            };
+                            break newline + 1;
+                        }
+                        None if buf.is_empty() => self.finish_record(),
+                        None => {
+                            let served = buf.len();
+                            self.progress = Progress::Line { served, end: false };
+                            break served;
+                        }
+                    }
+                }
+                Progress::Header { .. } => break 0,
+                Progress::Remaining { left, frame } => {
+                    let available = self.side_fill_buf(self.turn)?.len();
+                    match available {
+                        0 if frame => return Err(truncated()),
+                        0 => self.finish_record(),
+                        _ => break available.min(left),
+                    }
+                }
+            }
+        };
+        // Borrowing the fields separately lets the header be returned from the same `match`.
+        match &self.progress {
+            Progress::Header { header, pos, .. } => Ok(&header[*pos..]),
+            _ => match self.turn {
+                Side::Left => Ok(&self.left.fill_buf()?[..served]),
+                Side::Right => Ok(&self.right.fill_buf()?[..served]),
+            },
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt == 0 {
+            return;
+        }
+        let side = self.turn;
+        match self.progress {
+            Progress::Start => {}
+            Progress::Line { served, end } => {
+                self.side_consume(side, amt);
+                if end && amt >= served {
+                    self.finish_record();
+                } else {
+                    self.progress = Progress::Line {
+                        served: served.saturating_sub(amt),
+                        end,
+                    };
+                }
+            }
+            Progress::Header { header, pos, body } => {
+                let pos = (pos + amt).min(header.len());
+                self.progress = match (pos, body) {
+                    (4, 0) => {
+                        self.finish_record();
+                        return;
+                    }
+                    (4, _) => Progress::Remaining {
+                        left: body,
+                        frame: true,
+                    },
+                    _ => Progress::Header { header, pos, body },
+                };
+            }
+            Progress::Remaining { left, frame } => {
+                let amt = amt.min(left);
+                self.side_consume(side, amt);
+                if amt == left {
+                    self.finish_record();
+                } else {
+                    self.progress = Progress::Remaining {
+                        left: left - amt,
+                        frame,
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<R, S> Read for AlternatingBufRead<R, S>
+where
+    R: BufRead,
+    S: BufRead,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+/// Reader for alternating between the records of two unbuffered readers.
+///
+/// This is an [`AlternatingBufRead`] over a [`BufReader`] for each reader,
+/// see its documentation for more.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{AlternatingRead, Record};
+/// use std::io::Read;
+///
+/// let a: &[u8] = b"aaaa";
+/// let b: &[u8] = b"bbbbbb";
+///
+/// let mut reader = AlternatingRead::new(a, b).record(Record::Chunk(3));
+/// let mut merged = String::new();
+/// reader.read_to_string(&mut merged).unwrap();
+///
+/// assert_eq!(merged, "aaabbbabbb");
+/// ```
+#[derive(Debug)]
+pub struct AlternatingRead<R, S> {
+    inner: AlternatingBufRead<BufReader<R>, BufReader<S>>,
+}
+
+impl<R, S> AlternatingRead<R, S>
+where
+    R: Read,
+    S: Read,
+{
+    /// Create a new `AlternatingRead` taking turns line by line in [`Mode::Gapped`].
+    pub fn new(left: R, right: S) -> Self {
+        Self {
+            inner: AlternatingBufRead::new(BufReader::new(left), BufReader::new(right)),
+        }
+    }
+
+    /// Take turns with the given kind of [`Record`], which is [`Record::Line`] by default.
+    pub fn record(self, record: Record) -> Self {
+        Self {
+            inner: self.inner.record(record),
+        }
+    }
+
+    /// Handle exhaustion according to the given [`Mode`], which is [`Mode::Gapped`] by default.
+    pub fn mode(self, mode: Mode) -> Self {
+        Self {
+            inner: self.inner.mode(mode),
+        }
+    }
+
+    /// Unwraps this `AlternatingRead`, returning the underlying readers.
+    ///
+    /// Any data that was buffered but not read is lost.
+    pub fn into_inner(self) -> (R, S) {
+        let (left, right) = self.inner.into_inner();
+        (left.into_inner(), right.into_inner())
+    }
+}
+
+impl<R, S> Read for AlternatingRead<R, S>
+where
+    R: Read,
+    S: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+fn truncated() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "truncated frame")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlternatingExt;
+    use std::io::Cursor;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.split_inclusive('\n').map(String::from).collect()
+    }
+
+    fn read_all<R: Read>(mut reader: R) -> String {
+        let mut merged = String::new();
+        reader.read_to_string(&mut merged).unwrap();
+        merged
+    }
+
+    #[test]
+    fn lines_match_adaptors() {
+        for (a, b) in [
+            ("a1\na2\na3\n", "b1\n"),
+            ("a1\n", "b1\nb2\nb3"),
+            ("a1\na2", "b1\nb2\n"),
+            ("", "b1\n"),
+        ] {
+            let reader = |mode| AlternatingBufRead::new(Cursor::new(a), Cursor::new(b)).mode(mode);
+
+            let expected: String = lines(a).into_iter().alternate_with(lines(b)).collect();
+            assert_eq!(read_all(reader(Mode::Gapped)), expected);
+
+            let expected: String = lines(a).into_iter().alternate_with_all(lines(b)).collect();
+            assert_eq!(read_all(reader(Mode::All)), expected);
+
+            let expected: String = lines(a)
+                .into_iter()
+                .alternate_with_no_remainder(lines(b))
+                .collect();
+            assert_eq!(read_all(reader(Mode::NoRemainder)), expected);
+        }
+    }
+
+    #[test]
+    fn gapped_continues() {
+        let mut reader = AlternatingBufRead::new(Cursor::new("a\n"), Cursor::new("b1\nb2\n"));
+        let mut line = String::new();
+
+        assert_eq!(reader.read_line(&mut line).unwrap(), 2);
+        assert_eq!(reader.read_line(&mut line).unwrap(), 3);
+        assert_eq!(reader.read_line(&mut line).unwrap(), 0); // `a` is exhausted
+        assert_eq!(reader.read_line(&mut line).unwrap(), 3);
+        assert_eq!(line, "a\nb1\nb2\n");
+    }
+
+    #[test]
+    fn small_buffers() {
+        // Records span many refills of the underlying buffers.
+        let a = BufReader::with_capacity(2, Cursor::new("long line a\nx\n"));
+        let b = BufReader::with_capacity(3, Cursor::new("longer line b\ny"));
+        let reader = AlternatingBufRead::new(a, b).mode(Mode::All);
+
+        assert_eq!(read_all(reader), "long line a\nlonger line b\nx\ny");
+
+        let a = BufReader::with_capacity(2, Cursor::new("aaaaaaa"));
+        let b = BufReader::with_capacity(3, Cursor::new("bbbbb"));
+        let mut reader = AlternatingBufRead::new(a, b)
+            .record(Record::Chunk(4))
+            .mode(Mode::All);
+        let mut byte = [0];
+        let mut merged = Vec::new();
+        while reader.read(&mut byte).unwrap() > 0 {
+            merged.push(byte[0]);
+        }
+
+        assert_eq!(merged, b"aaaabbbbaaab");
+    }
+
+    #[test]
+    fn chunks() {
+        let reader =
+            AlternatingRead::new(&b"aaaaa"[..], &b"bbbbbbbbb"[..]).record(Record::Chunk(2));
+
+        assert_eq!(read_all(reader.mode(Mode::NoRemainder)), "aabbaabbabb");
+    }
+
+    fn frame(body: &str) -> Vec<u8> {
+        let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(body.as_bytes());
+        frame
+    }
+
+    #[test]
+    fn frames() {
+        let a = [frame("first"), frame(""), frame("third")].concat();
+        let b = frame("second");
+        let reader = AlternatingBufRead::new(Cursor::new(&a), Cursor::new(&b))
+            .record(Record::Frame)
+            .mode(Mode::All);
+        let mut merged = Vec::new();
+        BufReader::with_capacity(3, reader)
+            .read_to_end(&mut merged)
+            .unwrap();
+
+        let expected = [frame("first"), frame("second"), frame(""), frame("third")].concat();
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn truncated_frame() {
+        let a = [frame("ok"), frame("cut")[..5].to_vec()].concat();
+        let mut reader = AlternatingBufRead::new(Cursor::new(a), Cursor::new(frame("b")))
+            .record(Record::Frame)
+            .mode(Mode::All);
+        let mut merged = Vec::new();
+
+        let error = reader.read_to_end(&mut merged).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            merged,
+            [frame("ok"), frame("b"), frame("cut")[..5].to_vec()].concat()
+        );
+
+        let mut reader =
+            AlternatingBufRead::new(Cursor::new([0, 0]), Cursor::new([])).record(Record::Frame);
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,296 @@
+use std::sync::mpsc::{Receiver, RecvError, RecvTimeoutError, TryRecvError};
+use std::time::{Duration, Instant};
+
+#[allow(unused_imports)]
+use crate::AlternatingAll;
+
+/// Receives from several channels in turn, the way [`AlternatingAll`] takes items from two iterators.
+///
+/// Each receiver gets one turn per round and is skipped while it has nothing to receive,
+/// so no channel can hold back the others.
+/// Receivers whose senders have all been dropped are removed once they run empty.
+///
+/// Since a thread can only block on one channel at a time,
+/// [`recv`](Self::recv) waits on the receiver whose turn it is for at most the
+/// [poll interval](Self::poll_interval), then checks every receiver again.
+///
+/// Iterating receives until every sender is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::RoundRobinReceiver;
+/// use std::sync::mpsc;
+///
+/// let (a_tx, a_rx) = mpsc::channel();
+/// let (b_tx, b_rx) = mpsc::channel();
+/// a_tx.send(1).unwrap();
+/// a_tx.send(2).unwrap();
+/// b_tx.send(3).unwrap();
+/// drop((a_tx, b_tx));
+///
+/// let receiver = RoundRobinReceiver::new([a_rx, b_rx]);
+///
+/// assert_eq!(receiver.collect::<Vec<_>>(), [1, 3, 2]);
+/// ```
+#[derive(Debug)]
+pub struct RoundRobinReceiver<T> {
+    receivers: Vec<Receiver<T>>,
+    /// Index of the receiver whose turn it is.
+    turn: usize,
+    poll_interval: Duration,
+}
+
+impl<T> RoundRobinReceiver<T> {
+    /// The shortest [poll interval](Self::poll_interval), 10 microseconds.
+    pub const MIN_POLL_INTERVAL: Duration = Duration::from_micros(10);
+
+    /// Create a new `RoundRobinReceiver` taking turns in the order of `receivers`.
+    pub fn new(receivers: impl IntoIterator<Item = Receiver<T>>) -> Self {
+        Self {
+            receivers: receivers.into_iter().collect(),
+            turn: 0,
+            poll_interval: Duration::from_millis(1),
+        }
+    }
+
+    /// Set how long [`recv`](Self::recv) and [`recv_timeout`](Self::recv_timeout)
+    /// wait on one receiver before checking the others again. The default is 1 millisecond.
+    ///
+    /// An item sent to another receiver can wait up to this long to be received.
+    /// Intervals shorter than [`MIN_POLL_INTERVAL`](Self::MIN_POLL_INTERVAL) are raised to it,
+    /// since checking the receivers without waiting in between would keep a core busy.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval.max(Self::MIN_POLL_INTERVAL);
+        self
+    }
+
+    /// Number of receivers not yet found to be disconnected.
+    pub fn connected(&self) -> usize {
+        self.receivers.len()
+    }
+
+    /// Give every receiver one non-blocking turn, starting with the one whose turn it is,
+    /// and return the first item received.
+    ///
+    /// Fails with [`TryRecvError::Empty`] if no receiver had an item ready,
+    /// or [`TryRecvError::Disconnected`] once all senders have been dropped.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut index = self.turn;
+        for _ in 0..self.receivers.len() {
+            if index >= self.receivers.len() {
+                index = 0;
+            }
+            match self.receivers[index].try_recv() {
+                Ok(item) => {
+                    self.turn = index + 1;
+                    return Ok(item);
+                }
+                Err(TryRecvError::Empty) => index += 1,
+                // The next receiver moves into `index`.
+                Err(TryRecvError::Disconnected) => drop(self.receivers.remove(index)),
+            }
+        }
+        if self.receivers.is_empty() {
+            Err(TryRecvError::Disconnected)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    /// Block until one of the receivers has an item, taking turns when several do.
+    ///
+    /// Fails once all senders have been dropped.
+    pub fn recv(&mut self) -> Result<T, RecvError> {
+        self.recv_until(None).map_err(|_| RecvError)
+    }
+
+    /// Block until one of the receivers has an item, or until `timeout` has passed.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        // A deadline too far away to represent is no deadline.
+        self.recv_until(Instant::now().checked_add(timeout))
+    }
+
+    /// An iterator over the items that are ready, without blocking.
+    pub fn try_iter(&mut self) -> impl Iterator<Item = T> + '_ {
+        core::iter::from_fn(move || self.try_recv().ok())
+    }
+
+    fn recv_until(&mut self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        loop {
+            match self.try_recv() {
+                Ok(item) => return Ok(item),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {}
+            }
+            let wait = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(RecvTimeoutError::Timeout);
+                    }
+                    remaining.min(self.poll_interval)
+                }
+                None => self.poll_interval,
+            };
+            let index = self.turn % self.receivers.len();
+            match self.receivers[index].recv_timeout(wait) {
+                Ok(item) => {
+                    self.turn = index + 1;
+                    return Ok(item);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => drop(self.receivers.remove(index)),
+            }
+        }
+    }
+}
+
+impl<T> Iterator for RoundRobinReceiver<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlternatingExt;
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[test]
+    fn matches_all() {
+        let (a_tx, a_rx) = mpsc::channel();
+        let (b_tx, b_rx) = mpsc::sync_channel(8);
+        for n in 0..5 {
+            a_tx.send(n).unwrap();
+        }
+        for n in 10..12 {
+            b_tx.send(n).unwrap();
+        }
+        drop((a_tx, b_tx));
+
+        let receiver = RoundRobinReceiver::new([a_rx, b_rx]);
+
+        assert!(receiver.eq((0..5).alternate_with_all(10..12)));
+    }
+
+    #[test]
+    fn round_robin() {
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..3).map(|_| mpsc::channel()).unzip();
+        for (k, sender) in senders.iter().enumerate() {
+            for n in 0..2 {
+                sender.send(k * 10 + n).unwrap();
+            }
+        }
+        let mut receiver = RoundRobinReceiver::new(receivers);
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            [0, 10, 20, 1, 11, 21]
+        );
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        // The turn continues where it left off.
+        senders[0].send(2).unwrap();
+        senders[1].send(12).unwrap();
+        assert_eq!(receiver.try_recv(), Ok(2));
+        senders[0].send(3).unwrap();
+        assert_eq!(receiver.try_recv(), Ok(12));
+        assert_eq!(receiver.try_recv(), Ok(3));
+    }
+
+    #[test]
+    fn skips_disconnected() {
+        let (a_tx, a_rx) = mpsc::channel();
+        let (b_tx, b_rx) = mpsc::channel();
+        let (c_tx, c_rx) = mpsc::channel();
+        let mut receiver = RoundRobinReceiver::new([a_rx, b_rx, c_rx]);
+        a_tx.send(0).unwrap();
+        drop(b_tx);
+        c_tx.send(20).unwrap();
+        c_tx.send(21).unwrap();
+
+        assert_eq!(receiver.try_recv(), Ok(0));
+        assert_eq!(receiver.try_recv(), Ok(20));
+        assert_eq!(receiver.connected(), 2);
+        drop(a_tx);
+        assert_eq!(receiver.try_recv(), Ok(21));
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(receiver.connected(), 1);
+
+        drop(c_tx);
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(receiver.recv(), Err(RecvError));
+        assert_eq!(receiver.connected(), 0);
+    }
+
+    #[test]
+    fn blocks_until_any() {
+        let (a_tx, a_rx) = mpsc::channel::<i32>();
+        let (b_tx, b_rx) = mpsc::channel();
+        let mut receiver = RoundRobinReceiver::new([a_rx, b_rx]);
+
+        // `a` has the turn, but only `b` ever sends.
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            b_tx.send(10).unwrap();
+        });
+
+        assert_eq!(receiver.recv(), Ok(10));
+        sender.join().unwrap();
+        drop(a_tx);
+        assert_eq!(receiver.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn timeout() {
+        let (_a_tx, a_rx) = mpsc::channel::<i32>();
+        let (b_tx, b_rx) = mpsc::channel();
+        let mut receiver = RoundRobinReceiver::new([a_rx, b_rx]).poll_interval(Duration::ZERO);
+        assert_eq!(
+            receiver.poll_interval,
+            RoundRobinReceiver::<i32>::MIN_POLL_INTERVAL
+        );
+
+        let start = Instant::now();
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Timeout)
+        );
+        assert!(start.elapsed() >= Duration::from_millis(10));
+
+        b_tx.send(1).unwrap();
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(10)), Ok(1));
+    }
+
+    #[test]
+    fn ends_when_all_dropped() {
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..4).map(|_| mpsc::channel()).unzip();
+        let workers: Vec<_> = senders
+            .into_iter()
+            .enumerate()
+            .map(|(k, sender)| {
+                thread::spawn(move || {
+                    for n in 0..25 {
+                        sender.send(k * 100 + n).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        let mut received: Vec<_> = RoundRobinReceiver::new(receivers).collect();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        received.sort_unstable();
+        let expected: Vec<_> = (0..4)
+            .flat_map(|k| (0..25).map(move |n| k * 100 + n))
+            .collect();
+        assert_eq!(received, expected);
+    }
+}
@@ -0,0 +1,16 @@
+#[allow(unused_imports)]
+use crate::AlternatingBufRead;
+
+/// How a byte stream is divided into the records that take turns, as in [`AlternatingBufRead`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Record {
+    /// Up to and including a newline, `b'\n'`, or to the end of the stream.
+    Line,
+    /// A fixed number of bytes, except possibly at the end of the stream.
+    ///
+    /// A chunk size of zero is treated as one.
+    Chunk(usize),
+    /// A frame made of a 4-byte big-endian length followed by that many bytes.
+    /// The length is kept as part of the record.
+    Frame,
+}
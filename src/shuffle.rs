@@ -0,0 +1,338 @@
+/// Rearranges a slice laid out as `[left.., right..]`, split at `mid`,
+/// into the order of [`alternate_with_all`](crate::AlternatingExt::alternate_with_all).
+///
+/// This is a perfect out-shuffle when both halves have the same length,
+/// and the remaining items of the longer half are left at the end otherwise.
+/// It runs in `O(n)` time and uses no extra memory.
+///
+/// # Panics
+///
+/// Panics if `mid > slice.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::interleave_in_place;
+///
+/// let mut buffer = [1, 2, 3, 4, 5, 6, 7];
+/// interleave_in_place(&mut buffer, 2);
+/// assert_eq!(buffer, [1, 3, 2, 4, 5, 6, 7]);
+///
+/// let mut buffer = [1, 2, 3, 4, 5, 6, 7];
+/// interleave_in_place(&mut buffer, 5);
+/// assert_eq!(buffer, [1, 6, 2, 7, 3, 4, 5]);
+/// ```
+pub fn interleave_in_place<T>(slice: &mut [T], mid: usize) {
+    assert!(mid <= slice.len(), "mid > len");
+    let pairs = mid.min(slice.len() - mid);
+    // Move the right half next to the first `pairs` items of the left half.
+    slice[pairs..mid + pairs].rotate_right(pairs);
+    out_shuffle(&mut slice[..2 * pairs]);
+}
+
+/// Rearranges a slice in alternating order, `[l0, r0, l1, r1, ..]`, back into `[left.., right..]`,
+/// returning the length of the left half.
+///
+/// Inverse of [`interleave_in_place`] when the halves differ in length by at most one,
+/// with the extra item counted as part of the left half.
+/// See [`deinterleave_in_place_at`] for halves of any length.
+/// It runs in `O(n)` time and uses no extra memory.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::deinterleave_in_place;
+///
+/// let mut buffer = [1, 4, 2, 5, 3];
+/// assert_eq!(deinterleave_in_place(&mut buffer), 3);
+/// assert_eq!(buffer, [1, 2, 3, 4, 5]);
+/// ```
+pub fn deinterleave_in_place<T>(slice: &mut [T]) -> usize {
+    let mid = slice.len() - slice.len() / 2;
+    deinterleave_in_place_at(slice, mid);
+    mid
+}
+
+/// Rearranges a slice in the order of [`alternate_with_all`](crate::AlternatingExt::alternate_with_all)
+/// back into `[left.., right..]`, where the left half has length `mid`.
+///
+/// Inverse of [`interleave_in_place`] with the same `mid`.
+/// It runs in `O(n)` time and uses no extra memory.
+///
+/// # Panics
+///
+/// Panics if `mid > slice.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::deinterleave_in_place_at;
+///
+/// let mut buffer = [1, 3, 2, 4, 5, 6, 7];
+/// deinterleave_in_place_at(&mut buffer, 2);
+/// assert_eq!(buffer, [1, 2, 3, 4, 5, 6, 7]);
+/// ```
+pub fn deinterleave_in_place_at<T>(slice: &mut [T], mid: usize) {
+    assert!(mid <= slice.len(), "mid > len");
+    let pairs = mid.min(slice.len() - mid);
+    out_unshuffle(&mut slice[..2 * pairs]);
+    // Move the right half back after the rest of the left half.
+    slice[pairs..mid + pairs].rotate_left(pairs);
+}
+
+/// Rearranges a slice laid out as `[left.., right..]`, split at `mid`,
+/// into alternating order starting with the right half, `[r0, l0, r1, l1, ..]`.
+///
+/// This is a perfect in-shuffle when both halves have the same length,
+/// and the remaining items of the longer half are left at the end otherwise,
+/// as in [`alternate_with_all`](crate::AlternatingExt::alternate_with_all) with the right half first.
+/// It runs in `O(n)` time and uses no extra memory.
+///
+/// # Panics
+///
+/// Panics if `mid > slice.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::in_shuffle_in_place;
+///
+/// let mut buffer = [1, 2, 3, 4, 5, 6];
+/// in_shuffle_in_place(&mut buffer, 3);
+/// assert_eq!(buffer, [4, 1, 5, 2, 6, 3]);
+///
+/// let mut buffer = [1, 2, 3, 4, 5];
+/// in_shuffle_in_place(&mut buffer, 1);
+/// assert_eq!(buffer, [2, 1, 3, 4, 5]);
+/// ```
+pub fn in_shuffle_in_place<T>(slice: &mut [T], mid: usize) {
+    assert!(mid <= slice.len(), "mid > len");
+    let pairs = mid.min(slice.len() - mid);
+    slice[pairs..mid + pairs].rotate_right(pairs);
+    in_shuffle(&mut slice[..2 * pairs]);
+}
+
+/// Rearranges a slice in alternating order starting with the right half, `[r0, l0, r1, l1, ..]`,
+/// back into `[left.., right..]`, where the left half has length `mid`.
+///
+/// Inverse of [`in_shuffle_in_place`] with the same `mid`.
+/// It runs in `O(n)` time and uses no extra memory.
+///
+/// # Panics
+///
+/// Panics if `mid > slice.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::in_unshuffle_in_place;
+///
+/// let mut buffer = [4, 1, 5, 2, 6, 3];
+/// in_unshuffle_in_place(&mut buffer, 3);
+/// assert_eq!(buffer, [1, 2, 3, 4, 5, 6]);
+/// ```
+pub fn in_unshuffle_in_place<T>(slice: &mut [T], mid: usize) {
+    assert!(mid <= slice.len(), "mid > len");
+    let pairs = mid.min(slice.len() - mid);
+    in_unshuffle(&mut slice[..2 * pairs]);
+    slice[pairs..mid + pairs].rotate_left(pairs);
+}
+
+/// `[a1..an, b1..bn]` into `[a1, b1, .., an, bn]`.
+fn out_shuffle<T>(slice: &mut [T]) {
+    if slice.len() > 2 {
+        let end = slice.len() - 1;
+        in_shuffle(&mut slice[1..end]);
+    }
+}
+
+/// `[a1, b1, .., an, bn]` into `[a1..an, b1..bn]`.
+fn out_unshuffle<T>(slice: &mut [T]) {
+    if slice.len() > 2 {
+        let end = slice.len() - 1;
+        in_unshuffle(&mut slice[1..end]);
+    }
+}
+
+/// `[a1..an, b1..bn]` into `[b1, a1, .., bn, an]`.
+///
+/// This is the algorithm from Peiyush Jain's "A Simple In-Place Algorithm for In-Shuffle":
+/// repeatedly shuffle the largest prefix whose length is one less than a power of 3,
+/// which can be done by following the cycles starting at each smaller power of 3.
+fn in_shuffle<T>(mut slice: &mut [T]) {
+    while !slice.is_empty() {
+        let n = slice.len() / 2;
+        let (m, power) = block(n);
+        slice[m..n + m].rotate_right(m);
+        let (block, rest) = core::mem::take(&mut slice).split_at_mut(2 * m);
+        follow_cycles(block, power, |i| 2 * i % power);
+        slice = rest;
+    }
+}
+
+/// `[b1, a1, .., bn, an]` into `[a1..an, b1..bn]`, undoing [`in_shuffle`] step by step.
+fn in_unshuffle<T>(slice: &mut [T]) {
+    // Unshuffle every block, then undo the rotations from the last block to the first.
+    let (mut offset, mut n, mut levels) = (0, slice.len() / 2, 0);
+    while n > 0 {
+        let (m, power) = block(n);
+        // Multiplying by the inverse of 2 modulo `power` undoes doubling.
+        follow_cycles(&mut slice[offset..offset + 2 * m], power, |i| {
+            if i % 2 == 0 {
+                i / 2
+            } else {
+                (i + power) / 2
+            }
+        });
+        offset += 2 * m;
+        n -= m;
+        levels += 1;
+    }
+    for level in (0..levels).rev() {
+        let (mut offset, mut n) = (0, slice.len() / 2);
+        for _ in 0..level {
+            let (m, _) = block(n);
+            offset += 2 * m;
+            n -= m;
+        }
+        let (m, _) = block(n);
+        slice[offset + m..offset + n + m].rotate_left(m);
+    }
+}
+
+/// The number of pairs `m` to shuffle first out of `n`, and `2 * m + 1`, the largest power of 3 that fits.
+fn block(n: usize) -> (usize, usize) {
+    let mut power: usize = 3;
+    while let Some(next) = power.checked_mul(3).filter(|&next| next <= 2 * n + 1) {
+        power = next;
+    }
+    ((power - 1) / 2, power)
+}
+
+/// Move every item of `slice`, seen as 1-indexed, from `i` to `next(i)`,
+/// given that the cycles of `next` start at the powers of 3 below `power`.
+fn follow_cycles<T>(slice: &mut [T], power: usize, next: impl Fn(usize) -> usize) {
+    let mut leader = 1;
+    while leader < power {
+        let mut i = next(leader);
+        while i != leader {
+            slice.swap(leader - 1, i - 1);
+            i = next(i);
+        }
+        leader *= 3;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlternatingExt;
+
+    #[test]
+    fn interleave_matches_all() {
+        for len in 0..60 {
+            for mid in 0..=len {
+                let original: Vec<_> = (0..len).collect();
+                let (left, right) = original.split_at(mid);
+                let expected: Vec<_> = left.iter().alternate_with_all(right).copied().collect();
+
+                let mut buffer = original.clone();
+                interleave_in_place(&mut buffer, mid);
+
+                assert_eq!(buffer, expected, "len {}, mid {}", len, mid);
+            }
+        }
+    }
+
+    #[test]
+    fn in_shuffle_matches_all() {
+        for len in 0..60 {
+            for mid in 0..=len {
+                let original: Vec<_> = (0..len).collect();
+                let (left, right) = original.split_at(mid);
+                let expected: Vec<_> = right.iter().alternate_with_all(left).copied().collect();
+
+                let mut buffer = original.clone();
+                in_shuffle_in_place(&mut buffer, mid);
+                assert_eq!(buffer, expected, "len {}, mid {}", len, mid);
+
+                in_unshuffle_in_place(&mut buffer, mid);
+                assert_eq!(buffer, original, "len {}, mid {}", len, mid);
+            }
+        }
+    }
+
+    #[test]
+    fn deinterleave_at_round_trip() {
+        for len in 0..60 {
+            for mid in 0..=len {
+                let original: Vec<_> = (0..len).collect();
+
+                let mut buffer = original.clone();
+                interleave_in_place(&mut buffer, mid);
+                deinterleave_in_place_at(&mut buffer, mid);
+
+                assert_eq!(buffer, original, "len {}, mid {}", len, mid);
+            }
+        }
+    }
+
+    #[test]
+    fn deinterleave_round_trip() {
+        for len in 0..200 {
+            let original: Vec<_> = (0..len).collect();
+
+            let mut buffer = original.clone();
+            interleave_in_place(&mut buffer, len - len / 2);
+            assert_eq!(deinterleave_in_place(&mut buffer), len - len / 2);
+
+            assert_eq!(buffer, original, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn non_copy() {
+        let mut buffer: Vec<String> = ["a", "b", "c", "x", "y", "z"].map(String::from).into();
+
+        interleave_in_place(&mut buffer, 3);
+
+        assert_eq!(buffer, ["a", "x", "b", "y", "c", "z"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mid_out_of_bounds() {
+        interleave_in_place(&mut [1, 2, 3], 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn deinterleave_mid_out_of_bounds() {
+        deinterleave_in_place_at(&mut [1, 2, 3], 4);
+    }
+
+    #[test]
+    fn faro() {
+        let deck = 0..8;
+
+        assert_eq!(
+            deck.clone().faro_out().collect::<Vec<_>>(),
+            [0, 4, 1, 5, 2, 6, 3, 7]
+        );
+        assert_eq!(deck.faro_in().collect::<Vec<_>>(), [4, 0, 5, 1, 6, 2, 7, 3]);
+
+        assert_eq!((0..5).faro_out().collect::<Vec<_>>(), [0, 3, 1, 4, 2]);
+        assert_eq!((0..5).faro_in().collect::<Vec<_>>(), [2, 0, 3, 1, 4]);
+    }
+
+    #[test]
+    fn faro_out_repeats() {
+        // Eight out-shuffles restore a deck of 52 cards.
+        let mut deck: Vec<_> = (0..52).collect();
+        for _ in 0..8 {
+            deck = deck.into_iter().faro_out().collect();
+        }
+
+        assert!(deck.into_iter().eq(0..52));
+    }
+}
@@ -0,0 +1,19 @@
+/// One of the two iterators being alternated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The left iterator, which is the first in the sequence.
+    Left,
+    /// The right iterator.
+    Right,
+}
+
+impl Side {
+    /// The side that is not `self`.
+    #[cfg(any(feature = "std", feature = "futures-core"))]
+    pub(crate) fn other(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use core::mem;
+
+use crate::AlternatingExt;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Primitive types that [`interleave_slices`] and [`deinterleave_slices`] can move as raw bytes.
+///
+/// This trait is sealed and implemented for the integer and floating point types.
+pub trait Primitive: Copy + private::Sealed {}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {$(
+        impl private::Sealed for $ty {}
+        impl Primitive for $ty {}
+    )*};
+}
+impl_primitive!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64);
+
+/// Writes the items of `a` and `b` into `out` in the order of
+/// [`alternate_with_all`](AlternatingExt::alternate_with_all), returning the number of items written.
+///
+/// As many items are written as fit in `out`.
+/// On `x86_64`, the alternating part is done with SSE2, or AVX2 when the CPU supports it.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::interleave_slices;
+///
+/// let left = [1.0f32, 2.0];
+/// let right = [3.0, 4.0, 5.0];
+/// let mut out = [0.0; 8];
+///
+/// assert_eq!(interleave_slices(&left, &right, &mut out), 5);
+/// assert_eq!(out[..5], [1.0, 3.0, 2.0, 4.0, 5.0]);
+/// ```
+pub fn interleave_slices<T: Primitive>(a: &[T], b: &[T], out: &mut [T]) -> usize {
+    let total = out.len().min(a.len() + b.len());
+    let pairs = a.len().min(b.len()).min(total / 2);
+
+    let done = accelerated::interleave(&a[..pairs], &b[..pairs], &mut out[..2 * pairs]);
+    for k in done..pairs {
+        out[2 * k] = a[k];
+        out[2 * k + 1] = b[k];
+    }
+    let rest = a[pairs..].iter().alternate_with_all(&b[pairs..]);
+    for (slot, &item) in out[2 * pairs..total].iter_mut().zip(rest) {
+        *slot = item;
+    }
+    total
+}
+
+/// Splits `merged` into `a` and `b`, undoing [`interleave_slices`],
+/// and returns the number of items read from `merged`.
+///
+/// The lengths of `a` and `b` decide where each item goes, following
+/// [`alternate_with_all`](AlternatingExt::alternate_with_all).
+/// As many items are read as fit in `a` and `b`.
+/// On `x86_64`, the alternating part is done with SSE2, or AVX2 when the CPU supports it.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::deinterleave_slices;
+///
+/// let merged = [1u16, 3, 2, 4, 5];
+/// let mut left = [0; 2];
+/// let mut right = [0; 3];
+///
+/// assert_eq!(deinterleave_slices(&merged, &mut left, &mut right), 5);
+/// assert_eq!(left, [1, 2]);
+/// assert_eq!(right, [3, 4, 5]);
+/// ```
+pub fn deinterleave_slices<T: Primitive>(merged: &[T], a: &mut [T], b: &mut [T]) -> usize {
+    let total = merged.len().min(a.len() + b.len());
+    let pairs = a.len().min(b.len()).min(total / 2);
+
+    let done = accelerated::deinterleave(&merged[..2 * pairs], &mut a[..pairs], &mut b[..pairs]);
+    for k in done..pairs {
+        a[k] = merged[2 * k];
+        b[k] = merged[2 * k + 1];
+    }
+    let rest = a[pairs..].iter_mut().alternate_with_all(&mut b[pairs..]);
+    for (slot, &item) in rest.zip(&merged[2 * pairs..total]) {
+        *slot = item;
+    }
+    total
+}
+
+fn as_bytes<T: Primitive>(slice: &[T]) -> &[u8] {
+    // SAFETY: primitives have no padding, so every byte is initialized.
+    unsafe { core::slice::from_raw_parts(slice.as_ptr().cast(), mem::size_of_val(slice)) }
+}
+
+fn as_bytes_mut<T: Primitive>(slice: &mut [T]) -> &mut [u8] {
+    // SAFETY: primitives have no padding, and any bytes make a valid primitive.
+    unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), mem::size_of_val(slice)) }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod accelerated {
+    use core::mem;
+
+    use super::{as_bytes, as_bytes_mut, x86, Primitive};
+
+    /// Whether AVX2 can be used, detected at runtime if the standard library is available.
+    fn avx2() -> bool {
+        #[cfg(feature = "std")]
+        return std::is_x86_feature_detected!("avx2");
+        #[cfg(not(feature = "std"))]
+        return cfg!(target_feature = "avx2");
+    }
+
+    /// Interleave a prefix of the equally long `a` and `b` into `out`, returning its length.
+    pub(super) fn interleave<T: Primitive>(a: &[T], b: &[T], out: &mut [T]) -> usize {
+        let avx2 = avx2();
+        let (a, b, out) = (as_bytes(a), as_bytes(b), as_bytes_mut(out));
+        x86::interleave(mem::size_of::<T>(), a, b, out, avx2) / mem::size_of::<T>()
+    }
+
+    /// Deinterleave a prefix of `merged` into the equally long `a` and `b`, returning the length of either.
+    pub(super) fn deinterleave<T: Primitive>(merged: &[T], a: &mut [T], b: &mut [T]) -> usize {
+        let avx2 = avx2();
+        let (merged, a, b) = (as_bytes(merged), as_bytes_mut(a), as_bytes_mut(b));
+        x86::deinterleave(mem::size_of::<T>(), merged, a, b, avx2) / mem::size_of::<T>()
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+mod accelerated {
+    use super::Primitive;
+
+    pub(super) fn interleave<T: Primitive>(_: &[T], _: &[T], _: &mut [T]) -> usize {
+        0
+    }
+
+    pub(super) fn deinterleave<T: Primitive>(_: &[T], _: &mut [T], _: &mut [T]) -> usize {
+        0
+    }
+}
+
+/// Kernels working on raw bytes, for elements of `size` bytes.
+///
+/// `a` and `b` are at least as long as each other,
+/// and the merged slice is at least twice that long.
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    /// Returns the number of bytes of `a` handled.
+    pub(super) fn interleave(size: usize, a: &[u8], b: &[u8], out: &mut [u8], avx2: bool) -> usize {
+        let mut done = 0;
+        if avx2 {
+            // SAFETY: AVX2 is available.
+            done = unsafe {
+                match size {
+                    1 => interleave_avx2::<1>(a, b, out),
+                    2 => interleave_avx2::<2>(a, b, out),
+                    4 => interleave_avx2::<4>(a, b, out),
+                    _ => interleave_avx2::<8>(a, b, out),
+                }
+            };
+        }
+        let (a, b, out) = (&a[done..], &b[done..], &mut out[2 * done..]);
+        // SAFETY: SSE2 is always available on x86_64.
+        done + unsafe {
+            match size {
+                1 => interleave_sse2::<1>(a, b, out),
+                2 => interleave_sse2::<2>(a, b, out),
+                4 => interleave_sse2::<4>(a, b, out),
+                _ => interleave_sse2::<8>(a, b, out),
+            }
+        }
+    }
+
+    /// Returns the number of bytes of `a` handled.
+    pub(super) fn deinterleave(
+        size: usize,
+        merged: &[u8],
+        a: &mut [u8],
+        b: &mut [u8],
+        avx2: bool,
+    ) -> usize {
+        let mut done = 0;
+        if avx2 {
+            // SAFETY: AVX2 is available.
+            done = unsafe {
+                match size {
+                    1 => deinterleave_avx2::<1>(merged, a, b),
+                    2 => deinterleave_avx2::<2>(merged, a, b),
+                    4 => deinterleave_avx2::<4>(merged, a, b),
+                    _ => deinterleave_avx2::<8>(merged, a, b),
+                }
+            };
+        }
+        let (merged, a, b) = (&merged[2 * done..], &mut a[done..], &mut b[done..]);
+        // SAFETY: SSE2 is always available on x86_64.
+        done + unsafe {
+            match size {
+                1 => deinterleave_sse2::<1>(merged, a, b),
+                2 => deinterleave_sse2::<2>(merged, a, b),
+                4 => deinterleave_sse2::<4>(merged, a, b),
+                _ => deinterleave_sse2::<8>(merged, a, b),
+            }
+        }
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn interleave_sse2<const SIZE: usize>(a: &[u8], b: &[u8], out: &mut [u8]) -> usize {
+        let blocks = a.len().min(b.len()) / 16;
+        for k in 0..blocks {
+            let va = _mm_loadu_si128(a.as_ptr().add(16 * k).cast());
+            let vb = _mm_loadu_si128(b.as_ptr().add(16 * k).cast());
+            let (lo, hi) = match SIZE {
+                1 => (_mm_unpacklo_epi8(va, vb), _mm_unpackhi_epi8(va, vb)),
+                2 => (_mm_unpacklo_epi16(va, vb), _mm_unpackhi_epi16(va, vb)),
+                4 => (_mm_unpacklo_epi32(va, vb), _mm_unpackhi_epi32(va, vb)),
+                _ => (_mm_unpacklo_epi64(va, vb), _mm_unpackhi_epi64(va, vb)),
+            };
+            _mm_storeu_si128(out.as_mut_ptr().add(32 * k).cast(), lo);
+            _mm_storeu_si128(out.as_mut_ptr().add(32 * k + 16).cast(), hi);
+        }
+        blocks * 16
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn interleave_avx2<const SIZE: usize>(a: &[u8], b: &[u8], out: &mut [u8]) -> usize {
+        let blocks = a.len().min(b.len()) / 32;
+        for k in 0..blocks {
+            let va = _mm256_loadu_si256(a.as_ptr().add(32 * k).cast());
+            let vb = _mm256_loadu_si256(b.as_ptr().add(32 * k).cast());
+            // Unpacking works within each 128-bit lane, so the lanes are put back in order afterwards.
+            let (lo, hi) = match SIZE {
+                1 => (_mm256_unpacklo_epi8(va, vb), _mm256_unpackhi_epi8(va, vb)),
+                2 => (_mm256_unpacklo_epi16(va, vb), _mm256_unpackhi_epi16(va, vb)),
+                4 => (_mm256_unpacklo_epi32(va, vb), _mm256_unpackhi_epi32(va, vb)),
+                _ => (_mm256_unpacklo_epi64(va, vb), _mm256_unpackhi_epi64(va, vb)),
+            };
+            let first = _mm256_permute2x128_si256::<0x20>(lo, hi);
+            let second = _mm256_permute2x128_si256::<0x31>(lo, hi);
+            _mm256_storeu_si256(out.as_mut_ptr().add(64 * k).cast(), first);
+            _mm256_storeu_si256(out.as_mut_ptr().add(64 * k + 32).cast(), second);
+        }
+        blocks * 32
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn deinterleave_sse2<const SIZE: usize>(
+        merged: &[u8],
+        a: &mut [u8],
+        b: &mut [u8],
+    ) -> usize {
+        let blocks = a.len().min(b.len()) / 16;
+        for k in 0..blocks {
+            let v0 = _mm_loadu_si128(merged.as_ptr().add(32 * k).cast());
+            let v1 = _mm_loadu_si128(merged.as_ptr().add(32 * k + 16).cast());
+            let (va, vb) = match SIZE {
+                1 => {
+                    let mask = _mm_set1_epi16(0xFF);
+                    (
+                        _mm_packus_epi16(_mm_and_si128(v0, mask), _mm_and_si128(v1, mask)),
+                        _mm_packus_epi16(_mm_srli_epi16::<8>(v0), _mm_srli_epi16::<8>(v1)),
+                    )
+                }
+                2 => {
+                    // Sign extension keeps the signed saturation of the packing from changing anything.
+                    let low = |v| _mm_srai_epi32::<16>(_mm_slli_epi32::<16>(v));
+                    (
+                        _mm_packs_epi32(low(v0), low(v1)),
+                        _mm_packs_epi32(_mm_srai_epi32::<16>(v0), _mm_srai_epi32::<16>(v1)),
+                    )
+                }
+                4 => {
+                    let (f0, f1) = (_mm_castsi128_ps(v0), _mm_castsi128_ps(v1));
+                    (
+                        _mm_castps_si128(_mm_shuffle_ps::<0b10_00_10_00>(f0, f1)),
+                        _mm_castps_si128(_mm_shuffle_ps::<0b11_01_11_01>(f0, f1)),
+                    )
+                }
+                _ => (_mm_unpacklo_epi64(v0, v1), _mm_unpackhi_epi64(v0, v1)),
+            };
+            _mm_storeu_si128(a.as_mut_ptr().add(16 * k).cast(), va);
+            _mm_storeu_si128(b.as_mut_ptr().add(16 * k).cast(), vb);
+        }
+        blocks * 16
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn deinterleave_avx2<const SIZE: usize>(
+        merged: &[u8],
+        a: &mut [u8],
+        b: &mut [u8],
+    ) -> usize {
+        let blocks = a.len().min(b.len()) / 32;
+        for k in 0..blocks {
+            let v0 = _mm256_loadu_si256(merged.as_ptr().add(64 * k).cast());
+            let v1 = _mm256_loadu_si256(merged.as_ptr().add(64 * k + 32).cast());
+            // Same as with SSE2 within each 128-bit lane, leaving the 64-bit quarters out of order.
+            let (va, vb) = match SIZE {
+                1 => {
+                    let mask = _mm256_set1_epi16(0xFF);
+                    (
+                        _mm256_packus_epi16(_mm256_and_si256(v0, mask), _mm256_and_si256(v1, mask)),
+                        _mm256_packus_epi16(_mm256_srli_epi16::<8>(v0), _mm256_srli_epi16::<8>(v1)),
+                    )
+                }
+                2 => {
+                    let low = |v| _mm256_srai_epi32::<16>(_mm256_slli_epi32::<16>(v));
+                    (
+                        _mm256_packs_epi32(low(v0), low(v1)),
+                        _mm256_packs_epi32(
+                            _mm256_srai_epi32::<16>(v0),
+                            _mm256_srai_epi32::<16>(v1),
+                        ),
+                    )
+                }
+                4 => {
+                    let (f0, f1) = (_mm256_castsi256_ps(v0), _mm256_castsi256_ps(v1));
+                    (
+                        _mm256_castps_si256(_mm256_shuffle_ps::<0b10_00_10_00>(f0, f1)),
+                        _mm256_castps_si256(_mm256_shuffle_ps::<0b11_01_11_01>(f0, f1)),
+                    )
+                }
+                _ => (_mm256_unpacklo_epi64(v0, v1), _mm256_unpackhi_epi64(v0, v1)),
+            };
+            let va = _mm256_permute4x64_epi64::<0b11_01_10_00>(va);
+            let vb = _mm256_permute4x64_epi64::<0b11_01_10_00>(vb);
+            _mm256_storeu_si256(a.as_mut_ptr().add(32 * k).cast(), va);
+            _mm256_storeu_si256(b.as_mut_ptr().add(32 * k).cast(), vb);
+        }
+        blocks * 32
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn check(size: usize, avx2: bool) {
+            for len in 0..200 {
+                let a: Vec<u8> = (0..len).map(|n| n as u8).collect();
+                let b: Vec<u8> = (0..len).map(|n| !(n as u8)).collect();
+                let mut merged = vec![0; 2 * len];
+
+                let done = interleave(size, &a, &b, &mut merged, avx2);
+                assert_eq!(done, len / 16 * 16);
+                let expected: Vec<u8> = a[..done]
+                    .chunks(size)
+                    .zip(b[..done].chunks(size))
+                    .flat_map(|(a, b)| a.iter().chain(b))
+                    .copied()
+                    .collect();
+                assert_eq!(merged[..2 * done], expected, "size {}, len {}", size, len);
+
+                let (mut a2, mut b2) = (vec![0; len], vec![0; len]);
+                assert_eq!(deinterleave(size, &merged, &mut a2, &mut b2, avx2), done);
+                assert_eq!(a2[..done], a[..done], "size {}, len {}", size, len);
+                assert_eq!(b2[..done], b[..done], "size {}, len {}", size, len);
+            }
+        }
+
+        #[test]
+        fn sse2() {
+            for size in [1, 2, 4, 8] {
+                check(size, false);
+            }
+        }
+
+        #[test]
+        fn avx2() {
+            if std::is_x86_feature_detected!("avx2") {
+                for size in [1, 2, 4, 8] {
+                    check(size, true);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: Primitive + PartialEq + core::fmt::Debug>(make: impl Fn(usize) -> T) {
+        for a_len in (0..70).step_by(3) {
+            for b_len in (0..70).step_by(5) {
+                let a: Vec<T> = (0..a_len).map(&make).collect();
+                let b: Vec<T> = (1000..1000 + b_len).map(&make).collect();
+                let expected: Vec<T> = a.iter().alternate_with_all(&b).copied().collect();
+
+                let mut out = vec![make(0); a_len + b_len];
+                assert_eq!(interleave_slices(&a, &b, &mut out), a_len + b_len);
+                assert_eq!(out, expected);
+
+                let (mut a2, mut b2) = (vec![make(0); a_len], vec![make(0); b_len]);
+                assert_eq!(deinterleave_slices(&out, &mut a2, &mut b2), a_len + b_len);
+                assert_eq!((a2, b2), (a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn all_widths() {
+        round_trip(|n| n as u8);
+        round_trip(|n| n as i16);
+        round_trip(|n| n as f32);
+        round_trip(|n| n as u64);
+        round_trip(|n| n as f64);
+    }
+
+    #[test]
+    fn short_output() {
+        let a = [1u32; 40];
+        let b = [2u32; 40];
+        let mut out = [0; 33];
+
+        assert_eq!(interleave_slices(&a, &b, &mut out), 33);
+        assert!(a.iter().alternate_with_all(&b).take(33).eq(&out));
+    }
+
+    #[test]
+    fn short_input() {
+        let merged = [1i8, 2, 3, 4, 5];
+        let (mut a, mut b) = ([0; 4], [0; 4]);
+
+        assert_eq!(deinterleave_slices(&merged, &mut a, &mut b), 5);
+        assert_eq!(a, [1, 3, 5, 0]);
+        assert_eq!(b, [2, 4, 0, 0]);
+    }
+}
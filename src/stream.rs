@@ -0,0 +1,361 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+
+use crate::Side;
+
+/// Extension trait that provides methods for alternating between streams.
+///
+/// Requires the `futures-core` feature.
+pub trait AlternatingStreamExt: Stream {
+    /// Takes two streams and creates a new stream over both,
+    /// yielding whichever item is ready first.
+    ///
+    /// When both streams are ready, they take turns like [`Alternating`](crate::Alternating),
+    /// starting with the left one, so neither can starve the other.
+    /// The merged stream ends once both streams have ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::AlternatingStreamExt;
+    /// use core::pin::pin;
+    /// use futures_core::Stream;
+    /// use std::sync::Arc;
+    /// use std::task::{Context, Poll, Wake, Waker};
+    /// # struct Ready<I>(I);
+    /// # impl<I: Iterator + Unpin> Stream for Ready<I> {
+    /// #     type Item = I::Item;
+    /// #     fn poll_next(mut self: core::pin::Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<I::Item>> {
+    /// #         Poll::Ready(self.0.next())
+    /// #     }
+    /// # }
+    /// # struct Noop;
+    /// # impl Wake for Noop {
+    /// #     fn wake(self: Arc<Self>) {}
+    /// # }
+    ///
+    /// // Streams whose items are always ready.
+    /// let a = Ready([1, 2].into_iter());
+    /// let b = Ready([3, 4, 5].into_iter());
+    ///
+    /// let mut merged = pin!(a.merge_fair(b));
+    /// let waker = Waker::from(Arc::new(Noop));
+    /// let mut cx = Context::from_waker(&waker);
+    ///
+    /// assert_eq!(merged.as_mut().poll_next(&mut cx), Poll::Ready(Some(1))); // `a` first
+    /// assert_eq!(merged.as_mut().poll_next(&mut cx), Poll::Ready(Some(3))); // `b`
+    /// assert_eq!(merged.as_mut().poll_next(&mut cx), Poll::Ready(Some(2))); // `a`
+    /// assert_eq!(merged.as_mut().poll_next(&mut cx), Poll::Ready(Some(4))); // `b`
+    /// assert_eq!(merged.as_mut().poll_next(&mut cx), Poll::Ready(Some(5))); // `a` ended, so `b`
+    /// assert_eq!(merged.as_mut().poll_next(&mut cx), Poll::Ready(None));
+    /// ```
+    fn merge_fair<S>(self, other: S) -> MergeFair<Self, S>
+    where
+        Self: Sized,
+        S: Stream<Item = Self::Item>,
+    {
+        MergeFair::new(self, other)
+    }
+}
+
+impl<S> AlternatingStreamExt for S where S: Stream {}
+
+/// Stream for merging the items of two streams as they become ready,
+/// taking turns when both are.
+///
+/// This struct is created by the [`AlternatingStreamExt::merge_fair`] method, see its documentation for more.
+#[derive(Debug, Clone)]
+#[must_use = "streams do nothing unless polled"]
+pub struct MergeFair<S, T> {
+    left: S,
+    right: T,
+    /// The side that goes first when both are ready.
+    turn: Side,
+    left_done: bool,
+    right_done: bool,
+}
+
+impl<S, T> MergeFair<S, T>
+where
+    S: Stream,
+    T: Stream<Item = S::Item>,
+{
+    /// Create a new `MergeFair` stream from two other streams.
+    ///
+    /// Alternative to [`AlternatingStreamExt::merge_fair`]. There is no difference.
+    pub fn new(left: S, right: T) -> Self {
+        Self {
+            left,
+            right,
+            turn: Side::Left,
+            left_done: false,
+            right_done: false,
+        }
+    }
+}
+
+impl<S, T> MergeFair<S, T> {
+    /// Poll the stream on the given side, noting when it ends.
+    fn poll_side(
+        mut self: Pin<&mut Self>,
+        side: Side,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<S::Item>>
+    where
+        S: Stream,
+        T: Stream<Item = S::Item>,
+    {
+        let poll = match side {
+            Side::Left => {
+                // SAFETY: `left` is pinned structurally. It is never moved out of,
+                // `MergeFair` has no `Drop` impl that could move it,
+                // and the auto `Unpin` impl only applies when `left` is `Unpin` too.
+                unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.left) }.poll_next(cx)
+            }
+            Side::Right => {
+                // SAFETY: `right` is pinned structurally. It is never moved out of,
+                // `MergeFair` has no `Drop` impl that could move it,
+                // and the auto `Unpin` impl only applies when `right` is `Unpin` too.
+                unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.right) }.poll_next(cx)
+            }
+        };
+        if let Poll::Ready(None) = poll {
+            // SAFETY: the flags are not pinned, and no field is moved out of.
+            let this = unsafe { self.get_unchecked_mut() };
+            match side {
+                Side::Left => this.left_done = true,
+                Side::Right => this.right_done = true,
+            }
+        }
+        poll
+    }
+
+    fn is_done(&self, side: Side) -> bool {
+        match side {
+            Side::Left => self.left_done,
+            Side::Right => self.right_done,
+        }
+    }
+}
+
+impl<S, T> Stream for MergeFair<S, T>
+where
+    S: Stream,
+    T: Stream<Item = S::Item>,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let first = self.turn;
+        for side in [first, first.other()] {
+            if self.is_done(side) {
+                continue;
+            }
+            if let Poll::Ready(Some(item)) = self.as_mut().poll_side(side, cx) {
+                // SAFETY: `turn` is not pinned, and no field is moved out of.
+                unsafe { self.as_mut().get_unchecked_mut() }.turn = side.other();
+                return Poll::Ready(Some(item));
+            }
+        }
+        if self.left_done && self.right_done {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left_lower, left_upper) = match self.left_done {
+            true => (0, Some(0)),
+            false => self.left.size_hint(),
+        };
+        let (right_lower, right_upper) = match self.right_done {
+            true => (0, Some(0)),
+            false => self.right.size_hint(),
+        };
+        (
+            usize::saturating_add(left_lower, right_lower),
+            left_upper.and_then(|l| right_upper.and_then(|r| usize::checked_add(l, r))),
+        )
+    }
+}
+
+impl<S, T> FusedStream for MergeFair<S, T>
+where
+    S: Stream,
+    T: Stream<Item = S::Item>,
+{
+    fn is_terminated(&self) -> bool {
+        self.left_done && self.right_done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    /// A stream fed by hand, which wakes its task when fed.
+    #[derive(Default)]
+    struct Channel {
+        items: VecDeque<i32>,
+        closed: bool,
+        waker: Option<Waker>,
+    }
+
+    #[derive(Clone, Default)]
+    struct Manual(Rc<RefCell<Channel>>);
+
+    impl Manual {
+        fn send(&self, item: i32) {
+            let mut channel = self.0.borrow_mut();
+            channel.items.push_back(item);
+            if let Some(waker) = channel.waker.take() {
+                waker.wake();
+            }
+        }
+
+        fn close(&self) {
+            let mut channel = self.0.borrow_mut();
+            channel.closed = true;
+            if let Some(waker) = channel.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    impl Stream for Manual {
+        type Item = i32;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<i32>> {
+            let mut channel = self.0.borrow_mut();
+            match channel.items.pop_front() {
+                Some(item) => Poll::Ready(Some(item)),
+                None if channel.closed => Poll::Ready(None),
+                None => {
+                    channel.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// Counts how many times the task was woken.
+    #[derive(Default)]
+    struct Wakes(AtomicUsize);
+
+    impl Wake for Wakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Polls by hand, one step at a time.
+    struct Executor {
+        wakes: Arc<Wakes>,
+        waker: Waker,
+    }
+
+    impl Executor {
+        fn new() -> Self {
+            let wakes = Arc::new(Wakes::default());
+            let waker = Waker::from(Arc::clone(&wakes));
+            Self { wakes, waker }
+        }
+
+        fn poll<S: Stream + Unpin>(&self, stream: &mut S) -> Poll<Option<S::Item>> {
+            Pin::new(stream).poll_next(&mut Context::from_waker(&self.waker))
+        }
+
+        fn wakes(&self) -> usize {
+            self.wakes.0.load(Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn alternates_when_both_ready() {
+        let (a, b) = (Manual::default(), Manual::default());
+        let mut merged = a.clone().merge_fair(b.clone());
+        let executor = Executor::new();
+        for n in 0..3 {
+            a.send(n);
+            b.send(n + 10);
+        }
+
+        let polls: Vec<_> = (0..6).map(|_| executor.poll(&mut merged)).collect();
+
+        assert_eq!(polls, [0, 10, 1, 11, 2, 12].map(|n| Poll::Ready(Some(n))));
+        assert_eq!(executor.poll(&mut merged), Poll::Pending);
+    }
+
+    #[test]
+    fn does_not_wait_for_slow_side() {
+        let (a, b) = (Manual::default(), Manual::default());
+        let mut merged = a.clone().merge_fair(b.clone());
+        let executor = Executor::new();
+        b.send(10);
+        b.send(11);
+
+        assert_eq!(executor.poll(&mut merged), Poll::Ready(Some(10)));
+        assert_eq!(executor.poll(&mut merged), Poll::Ready(Some(11)));
+        assert_eq!(executor.poll(&mut merged), Poll::Pending);
+
+        a.send(0);
+        assert_eq!(executor.wakes(), 1);
+        b.send(12);
+        assert_eq!(executor.wakes(), 2);
+
+        // `b` went last, so `a` has priority.
+        assert_eq!(executor.poll(&mut merged), Poll::Ready(Some(0)));
+        assert_eq!(executor.poll(&mut merged), Poll::Ready(Some(12)));
+    }
+
+    #[test]
+    fn no_starvation() {
+        let (a, b) = (Manual::default(), Manual::default());
+        let mut merged = a.clone().merge_fair(b.clone());
+        let executor = Executor::new();
+
+        let mut from_b = Vec::new();
+        for n in 0..30 {
+            // `a` always has a backlog, while `b` only sometimes has an item.
+            a.send(n);
+            if n % 3 == 0 {
+                b.send(100 + n);
+            }
+            if let Poll::Ready(Some(item)) = executor.poll(&mut merged) {
+                if item >= 100 {
+                    from_b.push(item);
+                }
+            }
+        }
+
+        assert_eq!(from_b, (100..130).step_by(3).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ends_when_both_end() {
+        let (a, b) = (Manual::default(), Manual::default());
+        let mut merged = a.clone().merge_fair(b.clone());
+        let executor = Executor::new();
+        a.send(0);
+        a.close();
+
+        assert_eq!(executor.poll(&mut merged), Poll::Ready(Some(0)));
+        assert_eq!(executor.poll(&mut merged), Poll::Pending);
+        assert!(!merged.is_terminated());
+
+        b.close();
+        assert_eq!(executor.wakes(), 1);
+        assert_eq!(executor.poll(&mut merged), Poll::Ready(None));
+        assert!(merged.is_terminated());
+        assert_eq!(merged.size_hint(), (0, Some(0)));
+    }
+}
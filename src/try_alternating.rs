@@ -0,0 +1,439 @@
+use core::iter;
+
+#[allow(unused_imports)]
+use crate::AlternatingExt;
+use crate::{Alternating, AlternatingAll, AlternatingNoRemainder};
+
+/// What to do when one side of a [`TryAlternating`] yields an `Err`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ErrorPolicy {
+    /// Yield the error and end the iteration.
+    #[default]
+    ShortCircuit,
+    /// Set the error aside and stop pulling from the failing side, treating it as exhausted.
+    ///
+    /// The healthy side is then handled according to the exhaustion semantics
+    /// of the underlying adaptor. Only the first error of the side is kept,
+    /// since nothing more is pulled from it, and can be retrieved afterwards with
+    /// [`TryAlternating::left_error`] or [`TryAlternating::right_error`].
+    StopSide,
+    /// Set every error aside and keep pulling from the failing side, resuming with its next item.
+    ///
+    /// The errors can be retrieved afterwards with
+    /// [`TryAlternating::left_errors`] or [`TryAlternating::right_errors`].
+    /// Like [`Iterator::filter`], pulling never ends for a side that keeps yielding errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alternating_iter::{AlternatingExt, ErrorPolicy};
+    ///
+    /// let a = [Ok(1), Err("bad"), Ok(2), Err("worse")];
+    /// let b = [Ok(3), Ok(4), Ok(5)];
+    ///
+    /// let mut iter = a.into_iter()
+    ///     .try_alternate_with_all(b)
+    ///     .left_policy(ErrorPolicy::Collect);
+    ///
+    /// assert_eq!(iter.by_ref().collect::<Vec<_>>(), [Ok(1), Ok(3), Ok(2), Ok(4), Ok(5)]);
+    /// assert_eq!(iter.left_errors(), ["bad", "worse"]);
+    /// ```
+    #[cfg(feature = "std")]
+    Collect,
+}
+
+/// One side of a [`TryAlternating`], applying an [`ErrorPolicy`] to an iterator of `Result`s.
+///
+/// Errors under [`ErrorPolicy::ShortCircuit`] are passed through, while the error under
+/// [`ErrorPolicy::StopSide`] is stored and reported as exhaustion.
+/// After the first error, the side is fused, unless errors are collected
+/// under `ErrorPolicy::Collect` and skipped over.
+#[derive(Debug, Clone)]
+pub struct TrySide<I, E> {
+    iter: I,
+    policy: ErrorPolicy,
+    failed: bool,
+    error: Option<E>,
+    #[cfg(feature = "std")]
+    errors: Vec<E>,
+}
+
+impl<I, E> TrySide<I, E> {
+    fn new(iter: I) -> Self {
+        Self {
+            iter,
+            policy: ErrorPolicy::default(),
+            failed: false,
+            error: None,
+            #[cfg(feature = "std")]
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl<I, T, E> Iterator for TrySide<I, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let next = self.iter.next()?;
+        #[cfg(feature = "std")]
+        let next = match next {
+            Err(err) if self.policy == ErrorPolicy::Collect => {
+                self.errors.push(err);
+                loop {
+                    match self.iter.next()? {
+                        Err(err) => self.errors.push(err),
+                        ok => break ok,
+                    }
+                }
+            }
+            next => next,
+        };
+        match next {
+            Ok(item) => Some(Ok(item)),
+            Err(err) => {
+                self.failed = true;
+                match self.policy {
+                    ErrorPolicy::ShortCircuit => Some(Err(err)),
+                    ErrorPolicy::StopSide => {
+                        self.error = Some(err);
+                        None
+                    }
+                    #[cfg(feature = "std")]
+                    ErrorPolicy::Collect => unreachable!("errors are collected above"),
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.failed {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        match self.policy {
+            ErrorPolicy::ShortCircuit => (lower, upper),
+            _ => (0, upper),
+        }
+    }
+}
+
+impl<I, T, E> iter::FusedIterator for TrySide<I, E> where I: iter::FusedIterator<Item = Result<T, E>>
+{}
+
+/// Struct for alternating between two iterators of `Result`s.
+///
+/// This struct is created by the [`AlternatingExt::try_alternate_with`],
+/// [`AlternatingExt::try_alternate_with_all`] and [`AlternatingExt::try_alternate_with_no_remainder`]
+/// methods, see their documentation for more.
+///
+/// Each side follows its own [`ErrorPolicy`], [`ErrorPolicy::ShortCircuit`] by default.
+#[derive(Debug, Clone)]
+pub struct TryAlternating<A> {
+    inner: A,
+    done: bool,
+}
+
+impl<A> TryAlternating<A> {
+    pub(crate) fn new(inner: A) -> Self {
+        Self { inner, done: false }
+    }
+}
+
+macro_rules! impl_policies {
+    ($($adaptor:ident),*) => {$(
+        impl<I, J, T, E> TryAlternating<$adaptor<TrySide<I, E>, TrySide<J, E>>>
+        where
+            I: Iterator<Item = Result<T, E>>,
+            J: Iterator<Item = Result<T, E>>,
+        {
+            /// Set the [`ErrorPolicy`] of the left iterator.
+            pub fn left_policy(mut self, policy: ErrorPolicy) -> Self {
+                self.inner.i.policy = policy;
+                self
+            }
+
+            /// Set the [`ErrorPolicy`] of the right iterator.
+            pub fn right_policy(mut self, policy: ErrorPolicy) -> Self {
+                self.inner.j.policy = policy;
+                self
+            }
+
+            /// The error that stopped the left iterator under [`ErrorPolicy::StopSide`], if any.
+            pub fn left_error(&self) -> Option<&E> {
+                self.inner.i.error.as_ref()
+            }
+
+            /// The error that stopped the right iterator under [`ErrorPolicy::StopSide`], if any.
+            pub fn right_error(&self) -> Option<&E> {
+                self.inner.j.error.as_ref()
+            }
+
+            /// The errors of the left iterator collected under [`ErrorPolicy::Collect`], oldest first.
+            #[cfg(feature = "std")]
+            pub fn left_errors(&self) -> &[E] {
+                &self.inner.i.errors
+            }
+
+            /// The errors of the right iterator collected under [`ErrorPolicy::Collect`], oldest first.
+            #[cfg(feature = "std")]
+            pub fn right_errors(&self) -> &[E] {
+                &self.inner.j.errors
+            }
+        }
+    )*};
+}
+impl_policies!(Alternating, AlternatingAll, AlternatingNoRemainder);
+
+impl<A, T, E> Iterator for TryAlternating<A>
+where
+    A: Iterator<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = self.inner.next();
+        if let Some(Err(_)) = item {
+            self.done = true;
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // Any item may be an error that ends the iteration.
+        let (lower, upper) = self.inner.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<A, T, E> iter::FusedIterator for TryAlternating<A> where
+    A: iter::FusedIterator<Item = Result<T, E>>
+{
+}
+
+#[cfg(feature = "fallible-iterator")]
+impl<A, T, E> fallible_iterator::FallibleIterator for TryAlternating<A>
+where
+    A: Iterator<Item = Result<T, E>>,
+{
+    type Item = T;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<T>, E> {
+        Iterator::next(self).transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        Iterator::size_hint(self)
+    }
+}
+
+pub(crate) fn sides<I, J, E>(i: I, j: J) -> (TrySide<I, E>, TrySide<J, E>) {
+    (TrySide::new(i), TrySide::new(j))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_ok() {
+        let a: [Result<i32, &str>; 2] = [Ok(1), Ok(2)];
+        let b: [Result<i32, &str>; 3] = [Ok(3), Ok(4), Ok(5)];
+
+        let iter = a.into_iter().try_alternate_with_all(b);
+
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>(), Ok(vec![1, 3, 2, 4, 5]));
+    }
+
+    #[test]
+    fn short_circuit() {
+        let a = [Ok(1), Err("bad"), Ok(2)];
+        let b = [Ok(3), Ok(4), Ok(5)];
+
+        let mut iter = a.into_iter().try_alternate_with_all(b);
+
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), Some(Ok(3)));
+        assert_eq!(iter.next(), Some(Err("bad")));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn stop_side_all() {
+        let a = [Ok(1), Err("bad"), Ok(2)];
+        let b = [Ok(3), Ok(4), Ok(5)];
+
+        let mut iter = a
+            .into_iter()
+            .try_alternate_with_all(b)
+            .left_policy(ErrorPolicy::StopSide);
+
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), Some(Ok(3)));
+        assert_eq!(iter.next(), Some(Ok(4)));
+        assert_eq!(iter.next(), Some(Ok(5)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.left_error(), Some(&"bad"));
+        assert_eq!(iter.right_error(), None);
+    }
+
+    #[test]
+    fn stop_side_gapped() {
+        let a = [Ok(1), Ok(2)];
+        let b = [Ok(3), Err("bad"), Ok(4)];
+
+        let mut iter = a
+            .into_iter()
+            .try_alternate_with(b)
+            .right_policy(ErrorPolicy::StopSide);
+
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), Some(Ok(3)));
+        assert_eq!(iter.next(), Some(Ok(2)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.right_error(), Some(&"bad"));
+    }
+
+    #[test]
+    fn stop_side_no_remainder() {
+        let a = [Ok(1), Err("bad"), Ok(2)];
+        let b = [Ok(3), Ok(4), Ok(5)];
+
+        let mut iter = a
+            .into_iter()
+            .try_alternate_with_no_remainder(b)
+            .left_policy(ErrorPolicy::StopSide);
+
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), Some(Ok(3)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.left_error(), Some(&"bad"));
+    }
+
+    #[test]
+    fn stop_side_both() {
+        let a = [Ok(1), Err("left")];
+        let b = [Err("right"), Ok(2)];
+
+        let mut iter = a
+            .into_iter()
+            .try_alternate_with_all(b)
+            .left_policy(ErrorPolicy::StopSide)
+            .right_policy(ErrorPolicy::StopSide);
+
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.left_error(), Some(&"left"));
+        assert_eq!(iter.right_error(), Some(&"right"));
+    }
+
+    #[test]
+    fn stop_side_keeps_first_error() {
+        let mut pulled = 0;
+        let a = [Ok(1), Err("first"), Err("second"), Ok(2)]
+            .into_iter()
+            .inspect(|_| pulled += 1);
+        let b = [Ok(3), Ok(4)];
+
+        let mut iter = a
+            .try_alternate_with_all(b)
+            .left_policy(ErrorPolicy::StopSide);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), [Ok(1), Ok(3), Ok(4)]);
+        assert_eq!(iter.left_error(), Some(&"first"));
+        drop(iter);
+
+        assert_eq!(pulled, 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn collect_all() {
+        let a = [Ok(1), Err("a1"), Err("a2"), Ok(2), Ok(5), Err("a3")];
+        let b = [Ok(3), Ok(4)];
+
+        let mut iter = a
+            .into_iter()
+            .try_alternate_with_all(b)
+            .left_policy(ErrorPolicy::Collect);
+
+        assert_eq!(
+            iter.by_ref().collect::<Vec<_>>(),
+            [Ok(1), Ok(3), Ok(2), Ok(4), Ok(5)]
+        );
+        assert_eq!(iter.left_errors(), ["a1", "a2", "a3"]);
+        assert_eq!(iter.left_error(), None);
+        assert!(iter.right_errors().is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn collect_gapped_and_no_remainder() {
+        let a = [Ok(1), Ok(2)];
+        let b = [Err("b1"), Ok(3), Err("b2")];
+
+        let mut iter = a
+            .into_iter()
+            .try_alternate_with(b)
+            .right_policy(ErrorPolicy::Collect);
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), Some(Ok(3))); // after skipping `b1`
+        assert_eq!(iter.next(), Some(Ok(2)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.right_errors(), ["b1", "b2"]);
+
+        let a = [Ok(1), Err("a1"), Ok(2)];
+        let b = [Ok(3), Ok(4), Ok(5)];
+
+        let mut iter = a
+            .into_iter()
+            .try_alternate_with_no_remainder(b)
+            .left_policy(ErrorPolicy::Collect);
+        assert_eq!(
+            iter.by_ref().collect::<Vec<_>>(),
+            [Ok(1), Ok(3), Ok(2), Ok(4)]
+        );
+        assert_eq!(iter.left_errors(), ["a1"]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let a: [Result<i32, ()>; 3] = [Ok(1), Ok(2), Ok(3)];
+        let b: [Result<i32, ()>; 2] = [Ok(4), Ok(5)];
+
+        let iter = a.into_iter().try_alternate_with_all(b);
+        assert_eq!(iter.size_hint(), (1, Some(5)));
+
+        let mut iter = iter::once(Err(())).try_alternate_with_all(b);
+        assert_eq!(iter.next(), Some(Err(())));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[cfg(feature = "fallible-iterator")]
+    #[test]
+    fn fallible_iterator() {
+        use fallible_iterator::FallibleIterator;
+
+        let a = [Ok(1), Ok(2), Err("bad")];
+        let b = [Ok(3), Ok(4)];
+
+        let iter = a.into_iter().try_alternate_with_all(b);
+
+        assert_eq!(FallibleIterator::collect::<Vec<_>>(iter), Err("bad"));
+    }
+}
@@ -0,0 +1,407 @@
+use crate::utils::Rotation;
+use crate::Mode;
+
+/// The most iterators an [`AlternatingTuple`] can take turns between.
+const MAX: usize = 8;
+
+/// Takes two or more iterators, possibly with different [`Item`](Iterator::Item) types,
+/// and creates a new iterator over all of them in turn.
+///
+/// Each item is wrapped in the variant of an [`Either3`]-like enum telling which iterator it came from,
+/// `One` for the first, `Two` for the second and so on, without boxing.
+/// The result is an [`AlternatingTuple`], in [`Mode::Gapped`] by default.
+/// Prefix the iterators with `all:` or `no_remainder:` for the other modes.
+///
+/// Unlike nested [`alternate_with`](crate::AlternatingExt::alternate_with) calls,
+/// every iterator gets the same share of turns. Up to 8 iterators are supported.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{alternate, Either3};
+///
+/// let names = ["x", "y"];
+/// let counts = [1, 2, 3];
+/// let flags = [true];
+///
+/// let mut iter = alternate!(all: names, counts, flags);
+///
+/// assert_eq!(iter.next(), Some(Either3::One("x")));
+/// assert_eq!(iter.next(), Some(Either3::Two(1)));
+/// assert_eq!(iter.next(), Some(Either3::Three(true)));
+/// assert_eq!(iter.next(), Some(Either3::One("y")));
+/// assert_eq!(iter.next(), Some(Either3::Two(2)));
+/// assert_eq!(iter.next(), Some(Either3::Two(3)));
+/// assert_eq!(iter.next(), None);
+/// ```
+///
+/// The enum can be matched on to handle each type:
+///
+/// ```
+/// use alternating_iter::{alternate, Either2};
+///
+/// let described: Vec<String> = alternate!(no_remainder: 1..=3, "ab".chars())
+///     .map(|item| match item {
+///         Either2::One(n) => format!("number {}", n),
+///         Either2::Two(c) => format!("letter {}", c),
+///     })
+///     .collect();
+///
+/// assert_eq!(described, ["number 1", "letter a", "number 2", "letter b", "number 3"]);
+/// ```
+#[macro_export]
+macro_rules! alternate {
+    (gapped: $($iter:expr),+ $(,)?) => {
+        $crate::alternate!($($iter),+).mode($crate::Mode::Gapped)
+    };
+    (all: $($iter:expr),+ $(,)?) => {
+        $crate::alternate!($($iter),+).mode($crate::Mode::All)
+    };
+    (no_remainder: $($iter:expr),+ $(,)?) => {
+        $crate::alternate!($($iter),+).mode($crate::Mode::NoRemainder)
+    };
+    ($($iter:expr),+ $(,)?) => {
+        $crate::AlternatingTuple::from(($(::core::iter::IntoIterator::into_iter($iter),)+))
+    };
+}
+
+/// Struct for taking turns between the items of a tuple of iterators, which may differ in type.
+///
+/// Items are wrapped in [`Either2`] to [`Either8`] according to the number of iterators,
+/// and exhaustion is handled like in [`AlternatingArray`](crate::AlternatingArray).
+///
+/// This struct is created by the [`alternate!`] macro, see its documentation for more.
+/// It can also be created from a tuple of iterators with [`From`].
+#[derive(Debug, Clone)]
+pub struct AlternatingTuple<T> {
+    iters: T,
+    rotation: Rotation<MAX>,
+}
+
+impl<T> AlternatingTuple<T> {
+    /// Handle exhaustion according to the given [`Mode`], which is [`Mode::Gapped`] by default.
+    ///
+    /// Should be set before iterating, since iterators found exhausted so far are not remembered
+    /// in every mode.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.rotation.mode = mode;
+        self
+    }
+
+    /// The index of the iterator whose turn is next.
+    pub fn turn(&self) -> usize {
+        self.rotation.turn()
+    }
+
+    /// Unwraps this `AlternatingTuple`, returning the underlying iterators.
+    pub fn into_inner(self) -> T {
+        self.iters
+    }
+}
+
+/// Expands to `$ty` once per `$_ignored`, for repeating a type.
+macro_rules! same {
+    ($_ignored:ident, $ty:ty) => {
+        $ty
+    };
+}
+
+macro_rules! impl_tuple {
+    ($either:ident, $len:literal: $($T:ident $index:tt $variant:ident $ordinal:literal),+) => {
+        #[doc = concat!(
+            "Item of an [`AlternatingTuple`] over ", stringify!($len),
+            " iterators, telling which one it came from."
+        )]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $either<$($T),+> {
+            $(
+                #[doc = concat!("An item of the ", $ordinal, " iterator.")]
+                $variant($T),
+            )+
+        }
+
+        impl<T> $either<$(same!($T, T)),+> {
+            /// Returns the item, whichever iterator it came from.
+            pub fn into_inner(self) -> T {
+                match self {
+                    $(Self::$variant(item) => item,)+
+                }
+            }
+        }
+
+        impl<$($T),+> $either<$($T),+> {
+            /// The index of the iterator the item came from, starting at 0.
+            pub fn index(&self) -> usize {
+                match self {
+                    $(Self::$variant(_) => $index,)+
+                }
+            }
+        }
+
+        impl<$($T),+> From<($($T,)+)> for AlternatingTuple<($($T,)+)>
+        where
+            $($T: Iterator,)+
+        {
+            fn from(iters: ($($T,)+)) -> Self {
+                Self {
+                    iters,
+                    rotation: Rotation::new($len),
+                }
+            }
+        }
+
+        impl<$($T),+> Iterator for AlternatingTuple<($($T,)+)>
+        where
+            $($T: Iterator,)+
+        {
+            type Item = $either<$($T::Item),+>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let iters = &mut self.iters;
+                self.rotation.next(|k| match k {
+                    $($index => iters.$index.next().map($either::$variant),)+
+                    _ => unreachable!(),
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.rotation.size_hint(|k| match k {
+                    $($index => self.iters.$index.size_hint(),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+    };
+}
+impl_tuple!(Either2, 2: A 0 One "first", B 1 Two "second");
+impl_tuple!(Either3, 3: A 0 One "first", B 1 Two "second", C 2 Three "third");
+impl_tuple!(Either4, 4: A 0 One "first", B 1 Two "second", C 2 Three "third", D 3 Four "fourth");
+impl_tuple!(
+    Either5, 5: A 0 One "first", B 1 Two "second", C 2 Three "third", D 3 Four "fourth",
+    E 4 Five "fifth"
+);
+impl_tuple!(
+    Either6, 6: A 0 One "first", B 1 Two "second", C 2 Three "third", D 3 Four "fourth",
+    E 4 Five "fifth", F 5 Six "sixth"
+);
+impl_tuple!(
+    Either7, 7: A 0 One "first", B 1 Two "second", C 2 Three "third", D 3 Four "fourth",
+    E 4 Five "fifth", F 5 Six "sixth", G 6 Seven "seventh"
+);
+impl_tuple!(
+    Either8, 8: A 0 One "first", B 1 Two "second", C 2 Three "third", D 3 Four "fourth",
+    E 4 Five "fifth", F 5 Six "sixth", G 6 Seven "seventh", H 7 Eight "eighth"
+);
+
+/// Struct for taking fair turns between three or more iterators with the same [`Item`](Iterator::Item) type.
+///
+/// Exhaustion is handled like in the two-way adaptor it was created from,
+/// according to its [`Mode`], the same way as in [`AlternatingArray`](crate::AlternatingArray).
+///
+/// This struct is created by the `alternate_also` method of [`Alternating`](crate::Alternating),
+/// [`AlternatingAll`](crate::AlternatingAll) and [`AlternatingNoRemainder`](crate::AlternatingNoRemainder),
+/// and of this struct itself to add more iterators, up to 8.
+#[derive(Debug, Clone)]
+pub struct AlternatingMany<T> {
+    iters: T,
+    rotation: Rotation<MAX>,
+}
+
+impl<T> AlternatingMany<T> {
+    pub(crate) fn from_parts(iters: T, rotation: Rotation<MAX>) -> Self {
+        Self { iters, rotation }
+    }
+
+    /// Handle exhaustion according to the given [`Mode`] from now on.
+    ///
+    /// Should be set before iterating, since iterators found exhausted so far are not remembered
+    /// in every mode.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.rotation.mode = mode;
+        self
+    }
+
+    /// The index of the iterator whose turn is next.
+    pub fn turn(&self) -> usize {
+        self.rotation.turn()
+    }
+
+    /// Unwraps this `AlternatingMany`, returning the underlying iterators.
+    pub fn into_inner(self) -> T {
+        self.iters
+    }
+}
+
+macro_rules! impl_many {
+    ($A:ident, $($T:ident $index:tt),+) => {
+        impl<$A, $($T),+> Iterator for AlternatingMany<($A, $($T,)+)>
+        where
+            $A: Iterator,
+            $($T: Iterator<Item = $A::Item>,)+
+        {
+            type Item = $A::Item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let iters = &mut self.iters;
+                self.rotation.next(|k| match k {
+                    0 => iters.0.next(),
+                    $($index => iters.$index.next(),)+
+                    _ => unreachable!(),
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.rotation.size_hint(|k| match k {
+                    0 => self.iters.0.size_hint(),
+                    $($index => self.iters.$index.size_hint(),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+    };
+    ($A:ident, $($T:ident $index:tt),+; $Next:ident) => {
+        impl_many!($A, $($T $index),+);
+
+        impl<$A, $($T),+> AlternatingMany<($A, $($T,)+)>
+        where
+            $A: Iterator,
+            $($T: Iterator<Item = $A::Item>,)+
+        {
+            /// Add `other` to the rotation, taking its turn after the last iterator.
+            pub fn alternate_also<$Next>(
+                self,
+                other: $Next,
+            ) -> AlternatingMany<($A, $($T,)+ $Next::IntoIter)>
+            where
+                $Next: IntoIterator<Item = $A::Item>,
+            {
+                let iters = (self.iters.0, $(self.iters.$index,)+ other.into_iter());
+                AlternatingMany::from_parts(iters, self.rotation.grow())
+            }
+        }
+    };
+}
+impl_many!(A, B 1, C 2; D);
+impl_many!(A, B 1, C 2, D 3; E);
+impl_many!(A, B 1, C 2, D 3, E 4; F);
+impl_many!(A, B 1, C 2, D 3, E 4, F 5; G);
+impl_many!(A, B 1, C 2, D 3, E 4, F 5, G 6; H);
+impl_many!(A, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alternate_array, AlternatingExt};
+
+    #[test]
+    fn balanced_turns() {
+        let iter = alternate!(0..2, 10..12, 20..22).map(Either3::into_inner);
+
+        assert_eq!(iter.collect::<Vec<_>>(), [0, 10, 20, 1, 11, 21]);
+    }
+
+    #[test]
+    fn matches_array() {
+        let ranges = [0..3, 10..11, 20..24, 30..32];
+        for mode in [Mode::Gapped, Mode::All, Mode::NoRemainder] {
+            let [a, b, c, d] = ranges.clone();
+            let mut tuple = AlternatingTuple::from((a, b, c, d)).mode(mode);
+            let mut array = alternate_array(ranges.clone()).mode(mode);
+            for _ in 0..20 {
+                assert_eq!(tuple.size_hint(), array.size_hint(), "{:?}", mode);
+                assert_eq!(tuple.next().map(Either4::into_inner), array.next());
+                assert_eq!(tuple.turn(), array.turn());
+            }
+        }
+    }
+
+    #[test]
+    fn modes() {
+        let short = [1];
+        let long = "abc".chars();
+
+        assert_eq!(
+            alternate!(short, long.clone()).collect::<Vec<_>>(),
+            [Either2::One(1), Either2::Two('a')]
+        );
+        assert_eq!(
+            alternate!(all: short, long.clone())
+                .map(|item| item.index())
+                .collect::<Vec<_>>(),
+            [0, 1, 1, 1]
+        );
+        assert_eq!(alternate!(no_remainder: short, long).count(), 2);
+        let mut gapped = alternate!(gapped: short, "abc".chars());
+        assert_eq!(gapped.nth(2), None);
+        assert_eq!(gapped.next(), Some(Either2::Two('b')));
+    }
+
+    #[test]
+    fn eight() {
+        let iter = alternate!(all: [1u8], [2u16], [3u32], [4u64], [5i8], [6i16], [7i32], [8i64],);
+        let indices: Vec<usize> = iter.map(|item| item.index()).collect();
+
+        assert_eq!(indices, [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn into_inner() {
+        let mut iter = alternate!([1, 2], "ab".chars());
+        iter.next();
+
+        let (a, b) = iter.into_inner();
+        assert_eq!(a.as_slice(), [2]);
+        assert_eq!(b.as_str(), "ab");
+    }
+
+    #[test]
+    fn also_matches_array() {
+        let ranges = [0..3, 10..11, 20..24, 30..32];
+        for mode in [Mode::Gapped, Mode::All, Mode::NoRemainder] {
+            let [a, b, c, d] = ranges.clone();
+            let mut many = match mode {
+                Mode::Gapped => a.alternate_with(b).alternate_also(c),
+                Mode::All => a.alternate_with_all(b).alternate_also(c),
+                Mode::NoRemainder => a.alternate_with_no_remainder(b).alternate_also(c),
+            }
+            .alternate_also(d);
+            let mut array = alternate_array(ranges.clone()).mode(mode);
+            for _ in 0..20 {
+                assert_eq!(many.size_hint(), array.size_hint(), "{:?}", mode);
+                assert_eq!(many.next(), array.next(), "{:?}", mode);
+                assert_eq!(many.turn(), array.turn());
+            }
+        }
+    }
+
+    #[test]
+    fn also_continues_turns() {
+        let mut gapped = (0..3).alternate_with(10..13);
+        gapped.next();
+        let iter = gapped.alternate_also(20..23);
+        assert_eq!(iter.turn(), 1);
+        assert_eq!(iter.collect::<Vec<_>>(), [10, 20, 1, 11, 21, 2, 12, 22]);
+
+        let mut all = (0..1).alternate_with_all(10..14);
+        assert_eq!(all.by_ref().take(3).collect::<Vec<_>>(), [0, 10, 11]);
+        let iter = all.alternate_also(20..22);
+        assert_eq!(iter.collect::<Vec<_>>(), [12, 20, 13, 21]);
+
+        let mut no_remainder = (0..1).alternate_with_no_remainder(10..14);
+        assert_eq!(no_remainder.by_ref().count(), 2);
+        let mut iter = no_remainder.alternate_also(20..22);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn also_into_inner() {
+        let iter = [1, 2]
+            .into_iter()
+            .alternate_with_all([3])
+            .alternate_also([4]);
+
+        let (a, b, c) = iter.mode(Mode::NoRemainder).into_inner();
+        assert_eq!((a.len(), b.len(), c.len()), (2, 1, 1));
+    }
+}
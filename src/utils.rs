@@ -1,3 +1,7 @@
+use core::mem::MaybeUninit;
+
+use crate::{Mode, Side};
+
 pub(crate) fn min_and_1(i: usize, j: usize, last_i: bool) -> (usize, bool) {
     use core::cmp::Ordering;
 
@@ -14,3 +18,219 @@ pub(crate) fn checked((min, add_one): (usize, bool)) -> Option<usize> {
     min.checked_mul(2)
         .and_then(|min| min.checked_add(add_one as usize))
 }
+
+/// Write items from `iter` into `buf` until either runs out, returning how many were written.
+pub(crate) fn fill<I: Iterator>(iter: &mut I, buf: &mut [I::Item]) -> usize {
+    let mut written = 0;
+    for slot in buf {
+        match iter.next() {
+            Some(item) => *slot = item,
+            None => break,
+        }
+        written += 1;
+    }
+    written
+}
+
+/// Like [`fill`], initializing the written part of `buf`.
+pub(crate) fn fill_uninit<I: Iterator>(iter: &mut I, buf: &mut [MaybeUninit<I::Item>]) -> usize {
+    let mut written = 0;
+    for slot in buf {
+        match iter.next() {
+            Some(item) => slot.write(item),
+            None => break,
+        };
+        written += 1;
+    }
+    written
+}
+
+/// Bookkeeping shared by the adaptors: per-side counters and the first exhaustion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Observer<F> {
+    pub(crate) left: usize,
+    pub(crate) right: usize,
+    pub(crate) exhausted: Option<Side>,
+    hook: Option<F>,
+}
+
+impl<F> Observer<F> {
+    pub(crate) fn new() -> Self {
+        Self {
+            left: 0,
+            right: 0,
+            exhausted: None,
+            hook: None,
+        }
+    }
+
+    pub(crate) fn with_hook<G>(self, hook: G) -> Observer<G> {
+        Observer {
+            left: self.left,
+            right: self.right,
+            exhausted: self.exhausted,
+            hook: Some(hook),
+        }
+    }
+}
+
+impl<F> Observer<F>
+where
+    F: FnMut(Side),
+{
+    /// Record the result of pulling from `side`, passing it through.
+    pub(crate) fn record<T>(&mut self, side: Side, item: Option<T>) -> Option<T> {
+        match (&item, side) {
+            (Some(_), Side::Left) => self.left += 1,
+            (Some(_), Side::Right) => self.right += 1,
+            (None, _) => self.exhaust(side),
+        }
+        item
+    }
+
+    /// Record that `side` is exhausted, if no side was recorded before.
+    pub(crate) fn exhaust(&mut self, side: Side) {
+        if self.exhausted.is_none() {
+            self.exhausted = Some(side);
+            if let Some(hook) = &mut self.hook {
+                hook(side);
+            }
+        }
+    }
+}
+
+/// Turn order shared by the adaptors over more than two iterators,
+/// for up to `N` iterators of which the first `len` take part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Rotation<const N: usize> {
+    len: usize,
+    next: usize,
+    pub(crate) mode: Mode,
+    /// Marks the exhausted iterators in [`Mode::All`].
+    exhausted: [bool; N],
+    /// Marks the end of iteration in [`Mode::NoRemainder`].
+    done: bool,
+}
+
+impl<const N: usize> Rotation<N> {
+    pub(crate) fn new(len: usize) -> Self {
+        debug_assert!(len <= N);
+        Self {
+            len,
+            next: 0,
+            mode: Mode::Gapped,
+            exhausted: [false; N],
+            done: false,
+        }
+    }
+
+    /// Continue the turns of a two-way adaptor with a third iterator after the other two,
+    /// given the side whose turn is next and the side already found exhausted, if any.
+    pub(crate) fn from_two_way(mode: Mode, next: Side, exhausted: Option<Side>) -> Self {
+        let mut rotation = Self::new(3);
+        rotation.mode = mode;
+        rotation.next = next as usize;
+        match (mode, exhausted) {
+            (Mode::All, Some(side)) => rotation.exhausted[side as usize] = true,
+            (Mode::NoRemainder, Some(_)) => rotation.done = true,
+            _ => {}
+        }
+        rotation
+    }
+
+    /// Add an iterator after the last one.
+    pub(crate) fn grow(mut self) -> Self {
+        assert!(self.len < N);
+        self.len += 1;
+        self
+    }
+
+    /// The index of the iterator whose turn is next.
+    pub(crate) fn turn(&self) -> usize {
+        self.next
+    }
+
+    fn advance(&mut self) {
+        self.next = if self.next + 1 == self.len {
+            0
+        } else {
+            self.next + 1
+        };
+    }
+
+    /// Pull the next item with `pull`, given the index of the iterator to pull from.
+    pub(crate) fn next<T>(&mut self, mut pull: impl FnMut(usize) -> Option<T>) -> Option<T> {
+        if self.len == 0 || self.done {
+            return None;
+        }
+        match self.mode {
+            Mode::Gapped => {
+                let item = pull(self.next);
+                self.advance();
+                item
+            }
+            Mode::All => {
+                for _ in 0..self.len {
+                    let k = self.next;
+                    self.advance();
+                    if self.exhausted[k] {
+                        continue;
+                    }
+                    match pull(k) {
+                        Some(item) => return Some(item),
+                        None => self.exhausted[k] = true,
+                    }
+                }
+                None
+            }
+            Mode::NoRemainder => {
+                let item = pull(self.next);
+                match item {
+                    Some(_) => self.advance(),
+                    None => self.done = true,
+                }
+                item
+            }
+        }
+    }
+
+    /// Combine the size hints of the iterators, given by `hint` for each index.
+    pub(crate) fn size_hint(
+        &self,
+        hint: impl Fn(usize) -> (usize, Option<usize>),
+    ) -> (usize, Option<usize>) {
+        if self.len == 0 || self.done {
+            return (0, Some(0));
+        }
+        if self.mode == Mode::All {
+            let mut lower = 0usize;
+            let mut upper = Some(0usize);
+            for k in (0..self.len).filter(|&k| !self.exhausted[k]) {
+                let (iter_lower, iter_upper) = hint(k);
+                lower = lower.saturating_add(iter_lower);
+                upper = upper
+                    .zip(iter_upper)
+                    .and_then(|(upper, iter_upper)| upper.checked_add(iter_upper));
+            }
+            return (lower, upper);
+        }
+
+        // Until the first `None`, which comes on the turn after the last item
+        // of the shortest iterator, counting the turns of those before it in the rotation.
+        let mut lower = usize::MAX;
+        let mut upper = None;
+        for k in 0..self.len {
+            let before = (k + self.len - self.next) % self.len;
+            let (iter_lower, iter_upper) = hint(k);
+            lower = lower.min(iter_lower.saturating_mul(self.len).saturating_add(before));
+            let iter_upper = iter_upper
+                .and_then(|iter_upper| iter_upper.checked_mul(self.len))
+                .and_then(|turns| turns.checked_add(before));
+            upper = match (upper, iter_upper) {
+                (Some(upper), Some(iter_upper)) => Some(iter_upper.min(upper)),
+                (upper, iter_upper) => upper.or(iter_upper),
+            };
+        }
+        (lower, upper)
+    }
+}
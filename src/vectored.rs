@@ -0,0 +1,160 @@
+use std::io::{self, IoSlice, Write};
+
+use crate::AlternatingExt;
+
+/// Number of slices handed to each [`Write::write_vectored`] call at most.
+const BATCH: usize = 64;
+
+/// Writes every buffer of `a` and `b` to `writer` in the order of
+/// [`alternate_with_all`](AlternatingExt::alternate_with_all), without concatenating them,
+/// returning the number of bytes written.
+///
+/// The buffers are passed to [`Write::write_vectored`] in batches,
+/// and partial writes resume in the middle of the buffer they stopped at.
+///
+/// # Errors
+///
+/// Fails with the first error of `writer` other than [`io::ErrorKind::Interrupted`],
+/// or with [`io::ErrorKind::WriteZero`] if it stops accepting bytes.
+/// Some prefix of the interleaved output may already have been written by then,
+/// and its length is not reported, as with [`Write::write_all`].
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::write_all_alternating;
+///
+/// let headers = [&b"H1:"[..], b"H2:"];
+/// let bodies = [&b"one;"[..], b"two;"];
+/// let mut out = Vec::new();
+///
+/// let written = write_all_alternating(&mut out, headers, bodies).unwrap();
+///
+/// assert_eq!(out, b"H1:one;H2:two;");
+/// assert_eq!(written, 14);
+/// ```
+pub fn write_all_alternating<'a, W, I, J>(writer: &mut W, a: I, b: J) -> io::Result<usize>
+where
+    W: Write + ?Sized,
+    I: IntoIterator<Item = &'a [u8]>,
+    J: IntoIterator<Item = &'a [u8]>,
+{
+    let mut buffers = a
+        .into_iter()
+        .alternate_with_all(b)
+        .filter(|buffer| !buffer.is_empty());
+    let mut written = 0;
+    loop {
+        let mut batch = [IoSlice::new(&[]); BATCH];
+        let mut len = 0;
+        for (slot, buffer) in batch.iter_mut().zip(&mut buffers) {
+            *slot = IoSlice::new(buffer);
+            len += 1;
+        }
+        if len == 0 {
+            return Ok(written);
+        }
+
+        let mut slices = &mut batch[..len];
+        while !slices.is_empty() {
+            match writer.write_vectored(slices) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ))
+                }
+                Ok(n) => {
+                    written += n;
+                    IoSlice::advance_slices(&mut slices, n);
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accepts at most `limit` bytes per call, across slice boundaries,
+    /// and is interrupted every other call.
+    struct Partial {
+        out: Vec<u8>,
+        limit: usize,
+        calls: usize,
+        max_slices: usize,
+    }
+
+    impl Write for Partial {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.write_vectored(&[IoSlice::new(buf)])
+        }
+
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+            self.calls += 1;
+            self.max_slices = self.max_slices.max(bufs.len());
+            if self.calls % 2 == 0 {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let mut left = self.limit;
+            for buf in bufs {
+                let n = buf.len().min(left);
+                self.out.extend_from_slice(&buf[..n]);
+                left -= n;
+            }
+            Ok(self.limit - left)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn partial_writes() {
+        let a: Vec<Vec<u8>> = (0..100u8).map(|n| vec![n; n as usize % 4]).collect();
+        let b: Vec<Vec<u8>> = (0..30u8).map(|n| vec![200 + n; 3]).collect();
+        let expected: Vec<u8> = a.iter().alternate_with_all(&b).flatten().copied().collect();
+
+        for limit in [1, 2, 5, 7, 1000] {
+            let mut writer = Partial {
+                out: Vec::new(),
+                limit,
+                calls: 0,
+                max_slices: 0,
+            };
+            let written = write_all_alternating(
+                &mut writer,
+                a.iter().map(Vec::as_slice),
+                b.iter().map(Vec::as_slice),
+            )
+            .unwrap();
+
+            assert_eq!(writer.out, expected, "limit {}", limit);
+            assert_eq!(written, expected.len());
+            assert!(writer.max_slices <= BATCH);
+        }
+    }
+
+    #[test]
+    fn write_zero() {
+        let mut full: &mut [u8] = &mut [0; 3];
+
+        let error = write_all_alternating(&mut full, [&b"ab"[..]], [&b"cd"[..]]).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn empty() {
+        let mut out = Vec::new();
+
+        let written = write_all_alternating(&mut out, [&b""[..], b""], []).unwrap();
+
+        assert_eq!(written, 0);
+        assert!(out.is_empty());
+    }
+}
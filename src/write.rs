@@ -0,0 +1,352 @@
+use core::fmt;
+use std::error::Error;
+use std::io::{self, Write};
+
+use crate::{Record, Side};
+
+/// Writer for sending successive records to two writers in turn.
+///
+/// Whole [`Record`]s go to one writer and then the other, starting with the left writer.
+/// This undoes [`AlternatingBufRead`](crate::AlternatingBufRead) with the same kind of record.
+///
+/// # Errors
+///
+/// Errors of the writers are wrapped in a [`SinkError`] telling which one failed,
+/// keeping the original [`kind`](io::Error::kind).
+/// A writer that accepts no bytes fails with [`io::ErrorKind::WriteZero`].
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::AlternatingWrite;
+/// use std::io::Write;
+///
+/// let mut writer = AlternatingWrite::new(Vec::new(), Vec::new());
+/// writer.write_all(b"a1\nb1\na2\nb2\na3\n").unwrap();
+///
+/// let (a, b) = writer.into_inner();
+/// assert_eq!(a, b"a1\na2\na3\n");
+/// assert_eq!(b, b"b1\nb2\n");
+/// ```
+#[derive(Debug)]
+pub struct AlternatingWrite<V, W> {
+    left: V,
+    right: W,
+    record: Record,
+    turn: Side,
+    progress: Progress,
+}
+
+/// How far into its current record the writer whose turn it is has been written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Progress {
+    /// No part of the record has been written.
+    Start,
+    /// Within a line.
+    Line,
+    /// Within the length of a frame, of which `pos` bytes have been written.
+    Header { header: [u8; 4], pos: usize },
+    /// A chunk or the body of a frame, with this many bytes left.
+    Remaining(usize),
+}
+
+impl<V, W> AlternatingWrite<V, W>
+where
+    V: Write,
+    W: Write,
+{
+    /// Create a new `AlternatingWrite` taking turns line by line.
+    pub fn new(left: V, right: W) -> Self {
+        Self {
+            left,
+            right,
+            record: Record::Line,
+            turn: Side::Left,
+            progress: Progress::Start,
+        }
+    }
+}
+
+impl<V, W> AlternatingWrite<V, W> {
+    /// Take turns with the given kind of [`Record`], which is [`Record::Line`] by default.
+    ///
+    /// Should be set before writing, since the current record is finished the old way.
+    pub fn record(mut self, record: Record) -> Self {
+        self.record = record;
+        self
+    }
+
+    /// The writer the next byte goes to.
+    pub fn turn(&self) -> Side {
+        self.turn
+    }
+
+    /// Gets references to the underlying writers.
+    pub fn get_ref(&self) -> (&V, &W) {
+        (&self.left, &self.right)
+    }
+
+    /// Gets mutable references to the underlying writers.
+    pub fn get_mut(&mut self) -> (&mut V, &mut W) {
+        (&mut self.left, &mut self.right)
+    }
+
+    /// Unwraps this `AlternatingWrite`, returning the underlying writers.
+    pub fn into_inner(self) -> (V, W) {
+        (self.left, self.right)
+    }
+
+    fn finish_record(&mut self) {
+        self.progress = Progress::Start;
+        self.turn = self.turn.other();
+    }
+}
+
+impl<V, W> Write for AlternatingWrite<V, W>
+where
+    V: Write,
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.progress == Progress::Start {
+            self.progress = match self.record {
+                Record::Line => Progress::Line,
+                Record::Chunk(size) => Progress::Remaining(size.max(1)),
+                Record::Frame => Progress::Header {
+                    header: [0; 4],
+                    pos: 0,
+                },
+            };
+        }
+        let part = match self.progress {
+            Progress::Start => unreachable!(),
+            Progress::Line => match buf.iter().position(|&byte| byte == b'\n') {
+                Some(newline) => &buf[..=newline],
+                None => buf,
+            },
+            Progress::Header { pos, .. } => &buf[..buf.len().min(4 - pos)],
+            Progress::Remaining(left) => &buf[..buf.len().min(left)],
+        };
+        let side = self.turn;
+        let written = match side {
+            Side::Left => self.left.write(part),
+            Side::Right => self.right.write(part),
+        }
+        .map_err(|error| SinkError::wrap(side, error))?;
+        if written == 0 {
+            // Would otherwise be reported by `write_all` without saying which writer is full.
+            return Err(SinkError::wrap(
+                side,
+                io::Error::new(io::ErrorKind::WriteZero, "failed to write whole record"),
+            ));
+        }
+
+        match &mut self.progress {
+            Progress::Start => unreachable!(),
+            Progress::Line => {
+                if written == part.len() && part.ends_with(b"\n") {
+                    self.finish_record();
+                }
+            }
+            Progress::Header { header, pos } => {
+                header[*pos..*pos + written].copy_from_slice(&part[..written]);
+                *pos += written;
+                if *pos == 4 {
+                    match u32::from_be_bytes(*header) {
+                        0 => self.finish_record(),
+                        body => self.progress = Progress::Remaining(body as usize),
+                    }
+                }
+            }
+            Progress::Remaining(left) => {
+                *left -= written;
+                if *left == 0 {
+                    self.finish_record();
+                }
+            }
+        }
+        Ok(written)
+    }
+
+    /// Flush both writers, even if the first one fails.
+    fn flush(&mut self) -> io::Result<()> {
+        let left = self.left.flush();
+        let right = self.right.flush();
+        left.map_err(|error| SinkError::wrap(Side::Left, error))?;
+        right.map_err(|error| SinkError::wrap(Side::Right, error))
+    }
+}
+
+/// Error of one of the writers of an [`AlternatingWrite`].
+///
+/// It is returned inside an [`io::Error`] of the same kind, from which it can be recovered
+/// with [`io::Error::get_ref`] and `downcast_ref`.
+///
+/// # Examples
+///
+/// ```
+/// use alternating_iter::{AlternatingWrite, Side, SinkError};
+/// use std::io::{self, Write};
+///
+/// let full: &mut [u8] = &mut [];
+/// let mut writer = AlternatingWrite::new(Vec::new(), full);
+///
+/// let error = writer.write_all(b"fits\ndoes not fit\n").unwrap_err();
+/// let sink_error = error.get_ref().unwrap().downcast_ref::<SinkError>().unwrap();
+///
+/// assert_eq!(sink_error.side(), Side::Right);
+/// assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+/// ```
+#[derive(Debug)]
+pub struct SinkError {
+    side: Side,
+    error: io::Error,
+}
+
+impl SinkError {
+    fn wrap(side: Side, error: io::Error) -> io::Error {
+        io::Error::new(error.kind(), Self { side, error })
+    }
+
+    /// The writer that failed.
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    /// The error of the writer.
+    pub fn error(&self) -> &io::Error {
+        &self.error
+    }
+}
+
+impl fmt::Display for SinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.side {
+            Side::Left => write!(f, "left writer failed: {}", self.error),
+            Side::Right => write!(f, "right writer failed: {}", self.error),
+        }
+    }
+}
+
+impl Error for SinkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlternatingBufRead, Mode};
+    use std::io::{Cursor, Read};
+
+    /// Accepts one byte per call.
+    struct Trickle(Vec<u8>);
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.extend_from_slice(&buf[..buf.len().min(1)]);
+            Ok(buf.len().min(1))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Fails every call.
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::other("cannot flush"))
+        }
+    }
+
+    fn round_trip(record: Record, data: &[u8]) {
+        let mut writer = AlternatingWrite::new(Vec::new(), Trickle(Vec::new())).record(record);
+        // Write in uneven pieces to split records across calls.
+        for piece in data.chunks(3) {
+            writer.write_all(piece).unwrap();
+        }
+        let (a, Trickle(b)) = writer.into_inner();
+
+        let mut merged = Vec::new();
+        AlternatingBufRead::new(Cursor::new(a), Cursor::new(b))
+            .record(record)
+            .mode(Mode::All)
+            .read_to_end(&mut merged)
+            .unwrap();
+        assert_eq!(merged, data);
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(Record::Line, b"first\nsecond\n\nfourth\nfifth");
+        round_trip(Record::Chunk(4), b"abcdefghijklmnopq");
+
+        let mut frames = Vec::new();
+        for body in [&b"one"[..], b"", b"three", b"four"] {
+            frames.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            frames.extend_from_slice(body);
+        }
+        round_trip(Record::Frame, &frames);
+    }
+
+    #[test]
+    fn chunks() {
+        let mut writer = AlternatingWrite::new(Vec::new(), Vec::new()).record(Record::Chunk(2));
+
+        writer.write_all(b"aabbaab").unwrap();
+        assert_eq!(writer.turn(), Side::Right);
+
+        let (a, b) = writer.into_inner();
+        assert_eq!(a, b"aaaa");
+        assert_eq!(b, b"bbb");
+    }
+
+    #[test]
+    fn identifies_sink() {
+        let mut writer = AlternatingWrite::new(Vec::new(), Broken);
+
+        let error = writer.write_all(b"ok\nfails\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+        let sink_error = error
+            .get_ref()
+            .unwrap()
+            .downcast_ref::<SinkError>()
+            .unwrap();
+        assert_eq!(sink_error.side(), Side::Right);
+        assert_eq!(sink_error.to_string(), "right writer failed: broken");
+        assert_eq!(writer.get_ref().0, b"ok\n");
+
+        let mut writer = AlternatingWrite::new(Broken, Vec::new());
+        let error = writer.flush().unwrap_err();
+        let sink_error = error.into_inner().unwrap().downcast::<SinkError>().unwrap();
+        assert_eq!(sink_error.side(), Side::Left);
+        assert_eq!(sink_error.error().kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn flushes_both() {
+        let mut writer = AlternatingWrite::new(
+            io::BufWriter::new(Vec::new()),
+            io::BufWriter::new(Vec::new()),
+        );
+        writer.write_all(b"a\nb\n").unwrap();
+        assert!(writer.get_ref().1.get_ref().is_empty());
+
+        writer.flush().unwrap();
+
+        let (a, b) = writer.get_mut();
+        assert_eq!(a.get_ref(), b"a\n");
+        assert_eq!(b.get_ref(), b"b\n");
+    }
+}